@@ -0,0 +1,356 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    parse::{ParseStream, Parser},
+    spanned::Spanned,
+    Attribute, Error, Expr, FnArg, Ident, ItemFn, Pat, Path, Result, Token, Type,
+};
+
+/// how a prop gets its value when it is not set
+enum Init {
+    Required,
+    /// `#[prop(optional)]` on an `Option<T>`, setter takes `T`
+    Optional(Type),
+    /// `#[prop(default)]`, `#[prop(default = expr)]`, and `children`
+    Default(Option<Expr>),
+}
+
+struct Prop {
+    name: Ident,
+    ty: Type,
+    init: Init,
+    /// type parameter tracking whether a required prop is set
+    marker: Ident,
+}
+
+/// `T` of `Option<T>`
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let last = path.segments.last()?;
+    if last.ident != "Option" {
+        return None;
+    }
+    match &last.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// read and remove `#[prop(...)]` from a parameter
+fn prop_init(attrs: &mut Vec<Attribute>, ty: &Type, name: &Ident) -> Result<Init> {
+    let mut init = if name == "children" {
+        Init::Default(None)
+    } else {
+        Init::Required
+    };
+    let mut rest = vec![];
+    for attr in attrs.drain(..) {
+        if !attr.path.is_ident("prop") {
+            rest.push(attr);
+            continue;
+        }
+        init = attr.parse_args_with(|input: ParseStream| {
+            let key: Ident = input.parse()?;
+            if key == "optional" {
+                return match option_inner(ty) {
+                    Some(inner) => Ok(Init::Optional(inner.clone())),
+                    None => Err(Error::new_spanned(ty, "optional prop should be an Option")),
+                };
+            }
+            if key != "default" {
+                return Err(Error::new_spanned(
+                    key,
+                    "expect `optional`, `default` or `default = value`",
+                ));
+            }
+            if input.is_empty() {
+                return Ok(Init::Default(None));
+            }
+            input.parse::<Token![=]>()?;
+            Ok(Init::Default(Some(input.parse()?)))
+        })?;
+    }
+    *attrs = rest;
+    Ok(init)
+}
+
+/// `fn card_list` to `CardList`
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let mut chars = s.chars();
+            match chars.next() {
+                Some(c) => c.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// path of rtml, `::rtml` unless given as `crate = path`, for a renamed or re-exported rtml
+fn crate_path(attr: TokenStream) -> Result<Path> {
+    if attr.is_empty() {
+        return Ok(syn::parse_quote!(::rtml));
+    }
+    let parser = |input: ParseStream| {
+        input.parse::<Token![crate]>()?;
+        input.parse::<Token![=]>()?;
+        input.parse::<Path>()
+    };
+    parser.parse2(attr)
+}
+
+pub fn expand(attr: TokenStream, mut func: ItemFn) -> Result<TokenStream> {
+    let krate = crate_path(attr)?;
+    let sig = &func.sig;
+    if !sig.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &sig.generics,
+            "component can not be generic, props setters already accept `impl Into<T>`",
+        ));
+    }
+    if sig.asyncness.is_some() {
+        return Err(Error::new_spanned(
+            sig.asyncness,
+            "component can not be async",
+        ));
+    }
+
+    let mut props = vec![];
+    for input in func.sig.inputs.iter_mut() {
+        let input = match input {
+            FnArg::Typed(input) => input,
+            FnArg::Receiver(r) => return Err(Error::new_spanned(r, "component can not take self")),
+        };
+        let name = match input.pat.as_ref() {
+            Pat::Ident(pat) if pat.by_ref.is_none() && pat.subpat.is_none() => pat.ident.clone(),
+            pat => {
+                return Err(Error::new_spanned(
+                    pat,
+                    "component prop should be a plain name",
+                ))
+            }
+        };
+        let ty = input.ty.as_ref().clone();
+        match &ty {
+            Type::Reference(_) | Type::ImplTrait(_) => {
+                return Err(Error::new_spanned(
+                    &ty,
+                    "component prop should be an owned type, e.g. String instead of &str",
+                ))
+            }
+            _ => {}
+        }
+        let init = prop_init(&mut input.attrs, &ty, &name)?;
+        let marker = format_ident!("__{}", pascal_case(&name.to_string()));
+        props.push(Prop {
+            name,
+            ty,
+            init,
+            marker,
+        });
+    }
+
+    let vis = &func.vis;
+    let func_name = &func.sig.ident;
+    let name = Ident::new(&pascal_case(&func_name.to_string()), func_name.span());
+    let builder = format_ident!("{}Builder", name);
+    let docs: Vec<_> = func
+        .attrs
+        .iter()
+        .filter(|a| a.path.is_ident("doc"))
+        .collect();
+    let required: Vec<_> = props
+        .iter()
+        .filter(|p| matches!(p.init, Init::Required))
+        .collect();
+    let markers: Vec<_> = required.iter().map(|p| &p.marker).collect();
+
+    let fields = props.iter().map(|p| {
+        let Prop { name, ty, .. } = p;
+        match p.init {
+            Init::Required => {
+                let marker = &p.marker;
+                quote! { #name: #marker }
+            }
+            _ => quote! { #name: #ty },
+        }
+    });
+    let inits = props.iter().map(|p| {
+        let name = &p.name;
+        match &p.init {
+            Init::Required => quote! { #name: () },
+            Init::Optional(_) => quote! { #name: ::std::option::Option::None },
+            Init::Default(None) => quote! { #name: ::std::default::Default::default() },
+            Init::Default(Some(expr)) => quote_spanned! {expr.span()=> #name: #expr },
+        }
+    });
+    let unset = required.iter().map(|_| quote! { () });
+
+    // required setters move every field into a builder with the prop marked as set
+    let required_setters = required.iter().map(|p| {
+        let Prop {
+            name, ty, marker, ..
+        } = p;
+        let others: Vec<_> = markers.iter().filter(|m| *m != &marker).collect();
+        let before = markers.iter().map(|m| {
+            if *m == marker {
+                quote! { () }
+            } else {
+                quote! { #m }
+            }
+        });
+        let after = markers.iter().map(|m| {
+            if *m == marker {
+                quote! { (#ty,) }
+            } else {
+                quote! { #m }
+            }
+        });
+        let moved = props.iter().map(|other| {
+            let other = &other.name;
+            if other == name {
+                quote! { #other: (value.into(),) }
+            } else {
+                quote! { #other: self.#other }
+            }
+        });
+        quote! {
+            #[allow(non_camel_case_types)]
+            impl<#(#others),*> #builder<#(#before),*> {
+                pub fn #name(self, value: impl ::std::convert::Into<#ty>) -> #builder<#(#after),*> {
+                    #builder { #(#moved),* }
+                }
+            }
+        }
+    });
+    let other_setters = props.iter().filter_map(|p| {
+        let name = &p.name;
+        match &p.init {
+            Init::Required => None,
+            Init::Optional(inner) => Some(quote! {
+                pub fn #name(mut self, value: impl ::std::convert::Into<#inner>) -> Self {
+                    self.#name = ::std::option::Option::Some(value.into());
+                    self
+                }
+            }),
+            Init::Default(_) => {
+                let ty = &p.ty;
+                Some(quote! {
+                    pub fn #name(mut self, value: impl ::std::convert::Into<#ty>) -> Self {
+                        self.#name = value.into();
+                        self
+                    }
+                })
+            }
+        }
+    });
+    let set = required.iter().map(|p| {
+        let ty = &p.ty;
+        quote! { (#ty,) }
+    });
+    let args = props.iter().map(|p| {
+        let name = &p.name;
+        match p.init {
+            Init::Required => quote! { self.#name.0 },
+            _ => quote! { self.#name },
+        }
+    });
+    let required_doc = if required.is_empty() {
+        String::new()
+    } else {
+        let names: Vec<_> = required.iter().map(|p| format!("`{}`", p.name)).collect();
+        format!(", required props: {}", names.join(", "))
+    };
+    let builder_doc = format!("builder of [`{}`]{}", name, required_doc);
+    let build_doc = format!("run [`{}`] and keep the tag it returns", func_name);
+    let tag = quote! { #krate::Tag };
+
+    Ok(quote! {
+        #func
+
+        #(#docs)*
+        #vis struct #name(pub ::std::boxed::Box<dyn #tag>);
+
+        impl #name {
+            #[doc = #builder_doc]
+            #vis fn builder() -> #builder<#(#unset),*> {
+                #builder { #(#inits),* }
+            }
+        }
+
+        impl #tag for #name {
+            fn name(&self) -> &str {
+                self.0.name()
+            }
+
+            fn format(
+                &self,
+                f: &mut #krate::format::TagFormatter,
+                buf: &mut dyn ::std::fmt::Write,
+            ) -> ::std::fmt::Result {
+                self.0.format(f, buf)
+            }
+
+            fn as_element(&self) -> ::std::option::Option<&#krate::tags::UnitTag> {
+                self.0.as_element()
+            }
+
+            fn as_element_mut(&mut self) -> ::std::option::Option<&mut #krate::tags::UnitTag> {
+                self.0.as_element_mut()
+            }
+
+            fn as_any(&self) -> ::std::option::Option<&dyn ::std::any::Any> {
+                self.0.as_any()
+            }
+
+            fn as_any_mut(&mut self) -> ::std::option::Option<&mut dyn ::std::any::Any> {
+                self.0.as_any_mut()
+            }
+
+            fn categories(&self) -> &'static [#krate::content::Category] {
+                self.0.categories()
+            }
+
+            fn permits(&self, child: &dyn #tag) -> bool {
+                self.0.permits(child)
+            }
+        }
+
+        #krate::unchecked_content!(#name);
+
+        impl ::std::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                let mut formatter = #krate::format::TagFormatter::default();
+                self.0.format(&mut formatter, f)
+            }
+        }
+
+        #[doc = #builder_doc]
+        #[allow(non_camel_case_types)]
+        #vis struct #builder<#(#markers),*> {
+            #(#fields),*
+        }
+
+        #(#required_setters)*
+
+        #[allow(non_camel_case_types)]
+        impl<#(#markers),*> #builder<#(#markers),*> {
+            #(#other_setters)*
+        }
+
+        impl #builder<#(#set),*> {
+            #[doc = #build_doc]
+            pub fn build(self) -> #name {
+                #name(::std::boxed::Box::new(#func_name(#(#args),*)))
+            }
+        }
+    })
+}
@@ -0,0 +1,462 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    token, Error, Expr, Ident, Lit, Pat, Result, Token,
+};
+
+use crate::prop::{parse_name, Prop, PropInput};
+
+/// elements without end tag, same as `rtml::tags::VOID_ELEMENTS`
+const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// `attr`, `attr="literal"` or `attr={expr}`
+pub struct Attr {
+    pub tokens: TokenStream,
+    pub name: String,
+    pub value: Option<Expr>,
+}
+
+impl Parse for Attr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let (tokens, name) = parse_name(input)?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            if input.peek(Lit) {
+                Some(Expr::Lit(syn::ExprLit {
+                    attrs: vec![],
+                    lit: input.parse()?,
+                }))
+            } else if input.peek(token::Brace) {
+                let content;
+                braced!(content in input);
+                Some(content.parse()?)
+            } else {
+                return Err(input.error("attribute value should be a literal or `{expression}`"));
+            }
+        } else {
+            None
+        };
+        Ok(Self {
+            tokens,
+            name,
+            value,
+        })
+    }
+}
+
+/// `<name attrs...>children...</name>`, `<name attrs... />`, or a void element `<name attrs...>`
+pub struct Element {
+    pub tokens: TokenStream,
+    pub name: String,
+    pub attrs: Vec<Attr>,
+    pub children: Vec<Node>,
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<Token![<]>()?;
+        let (tokens, name) = parse_name(input)?;
+        let mut attrs = vec![];
+        while !(input.is_empty() || input.peek(Token![>]) || input.peek(Token![/])) {
+            attrs.push(input.parse()?);
+        }
+        let mut element = Self {
+            tokens,
+            name,
+            attrs,
+            children: vec![],
+        };
+        if input.peek(Token![/]) {
+            input.parse::<Token![/]>()?;
+            input.parse::<Token![>]>()?;
+            return Ok(element);
+        }
+        input.parse::<Token![>]>()?;
+        if VOID_ELEMENTS.contains(&element.name.as_str()) {
+            return Ok(element);
+        }
+        loop {
+            if input.is_empty() {
+                let msg = format!("<{}> is not closed", element.name);
+                return Err(Error::new_spanned(&element.tokens, msg));
+            }
+            if input.peek(Token![<]) && input.peek2(Token![/]) {
+                break;
+            }
+            element.children.push(input.parse()?);
+        }
+        input.parse::<Token![<]>()?;
+        input.parse::<Token![/]>()?;
+        let (close, close_name) = parse_name(input)?;
+        if close_name != element.name {
+            let msg = format!("expect </{}>, found </{}>", element.name, close_name);
+            return Err(Error::new_spanned(close, msg));
+        }
+        input.parse::<Token![>]>()?;
+        Ok(element)
+    }
+}
+
+/// condition of `if`, may be `let pattern = expr`
+pub struct Condition(TokenStream);
+
+impl Parse for Condition {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut tokens = TokenStream::new();
+        if input.peek(Token![let]) {
+            input.parse::<Token![let]>()?.to_tokens(&mut tokens);
+            parse_pat(input)?.to_tokens(&mut tokens);
+            input.parse::<Token![=]>()?.to_tokens(&mut tokens);
+        }
+        input
+            .call(Expr::parse_without_eager_brace)?
+            .to_tokens(&mut tokens);
+        Ok(Self(tokens))
+    }
+}
+
+/// pattern with optional `|` alternatives
+fn parse_pat(input: ParseStream) -> Result<Pat> {
+    let leading_vert: Option<Token![|]> = input.parse()?;
+    let first: Pat = input.parse()?;
+    if leading_vert.is_none() && !input.peek(Token![|]) {
+        return Ok(first);
+    }
+    let mut cases = syn::punctuated::Punctuated::new();
+    cases.push_value(first);
+    while input.peek(Token![|]) {
+        cases.push_punct(input.parse()?);
+        cases.push_value(input.parse()?);
+    }
+    Ok(Pat::Or(syn::PatOr {
+        attrs: vec![],
+        leading_vert,
+        cases,
+    }))
+}
+
+/// `{ nodes... }`
+fn parse_block(input: ParseStream) -> Result<Vec<Node>> {
+    let content;
+    braced!(content in input);
+    let mut nodes = vec![];
+    while !content.is_empty() {
+        nodes.push(content.parse()?);
+    }
+    Ok(nodes)
+}
+
+pub struct Arm {
+    pub pat: Pat,
+    pub guard: Option<Expr>,
+    pub body: Vec<Node>,
+}
+
+pub enum Node {
+    Element(Element),
+    /// quoted text or a number
+    Text(Lit),
+    /// `{expr}`, anything converts into `Children`
+    Expr(Expr),
+    For {
+        pat: Pat,
+        expr: Expr,
+        body: Vec<Node>,
+    },
+    If {
+        branches: Vec<(Condition, Vec<Node>)>,
+        otherwise: Option<Vec<Node>>,
+    },
+    Match {
+        expr: Expr,
+        arms: Vec<Arm>,
+    },
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![<]) {
+            return Ok(Node::Element(input.parse()?));
+        }
+        if input.peek(Lit) {
+            return Ok(Node::Text(input.parse()?));
+        }
+        if input.peek(token::Brace) {
+            let content;
+            braced!(content in input);
+            return Ok(Node::Expr(content.parse()?));
+        }
+        if input.peek(Token![for]) {
+            input.parse::<Token![for]>()?;
+            let pat = parse_pat(input)?;
+            input.parse::<Token![in]>()?;
+            let expr = input.call(Expr::parse_without_eager_brace)?;
+            let body = parse_block(input)?;
+            return Ok(Node::For { pat, expr, body });
+        }
+        if input.peek(Token![if]) {
+            input.parse::<Token![if]>()?;
+            let mut branches = vec![(input.parse()?, parse_block(input)?)];
+            let mut otherwise = None;
+            while input.peek(Token![else]) {
+                input.parse::<Token![else]>()?;
+                if input.peek(Token![if]) {
+                    input.parse::<Token![if]>()?;
+                    branches.push((input.parse()?, parse_block(input)?));
+                } else {
+                    otherwise = Some(parse_block(input)?);
+                    break;
+                }
+            }
+            return Ok(Node::If {
+                branches,
+                otherwise,
+            });
+        }
+        if input.peek(Token![match]) {
+            input.parse::<Token![match]>()?;
+            let expr = input.call(Expr::parse_without_eager_brace)?;
+            let content;
+            braced!(content in input);
+            let mut arms = vec![];
+            while !content.is_empty() {
+                let pat = parse_pat(&content)?;
+                let guard = if content.peek(Token![if]) {
+                    content.parse::<Token![if]>()?;
+                    Some(content.parse()?)
+                } else {
+                    None
+                };
+                content.parse::<Token![=>]>()?;
+                let body = if content.peek(Token![<]) {
+                    vec![Node::Element(content.parse()?)]
+                } else {
+                    parse_block(&content)?
+                };
+                content.parse::<Option<Token![,]>>()?;
+                arms.push(Arm { pat, guard, body });
+            }
+            return Ok(Node::Match { expr, arms });
+        }
+        Err(input.error("expect an element, quoted text, `{expression}`, `for`, `if` or `match`"))
+    }
+}
+
+/// `$crate; nodes...`
+pub struct HtmlInput {
+    pub krate: TokenTree,
+    pub nodes: Vec<Node>,
+}
+
+impl Parse for HtmlInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let krate = input.parse()?;
+        input.parse::<Token![;]>()?;
+        let mut nodes = vec![];
+        while !input.is_empty() {
+            nodes.push(input.parse()?);
+        }
+        Ok(Self { krate, nodes })
+    }
+}
+
+impl HtmlInput {
+    /// a single root element expands to its tag, anything else to `Children`
+    pub fn expand(&self) -> Result<TokenStream> {
+        if let [Node::Element(element)] = self.nodes.as_slice() {
+            return self.element(element);
+        }
+        self.children(&self.nodes, false)
+    }
+
+    fn element(&self, element: &Element) -> Result<TokenStream> {
+        if element.name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            return self.component(element);
+        }
+        let krate = &self.krate;
+        let void = VOID_ELEMENTS.contains(&element.name.as_str());
+        if void && !element.children.is_empty() {
+            let msg = format!("void element <{}> can not have children", element.name);
+            return Err(Error::new_spanned(&element.tokens, msg));
+        }
+
+        let mut props = vec![];
+        let mut style = None;
+        let mut handlers = vec![];
+        let handlers_var = Ident::new("handlers", Span::mixed_site());
+        for attr in element.attrs.iter() {
+            let lower = attr.name.to_ascii_lowercase();
+            if lower == "style" || lower.starts_with("on") {
+                let value = match &attr.value {
+                    Some(value) => value,
+                    None => {
+                        let msg = format!("{} needs a value", attr.name);
+                        return Err(Error::new_spanned(&attr.tokens, msg));
+                    }
+                };
+                if lower == "style" {
+                    if style.is_some() {
+                        return Err(Error::new_spanned(
+                            &attr.tokens,
+                            "duplicate attribute style",
+                        ));
+                    }
+                    style = Some(value);
+                } else {
+                    let event = lower.strip_prefix("on").unwrap_or(&lower).trim_start_matches('-');
+                    let ident = event.chars().enumerate().all(|(i, c)| {
+                        c == '_' || c == '$' || c.is_alphabetic() || (i > 0 && c.is_alphanumeric())
+                    });
+                    if event.is_empty() || !ident {
+                        let msg = format!("invalid event name {:?} in {}", event, attr.name);
+                        return Err(Error::new_spanned(&attr.tokens, msg));
+                    }
+                    handlers.push(quote_spanned! {value.span()=>
+                        #handlers_var.insert(#event, #value);
+                    });
+                }
+                continue;
+            }
+            props.push(Prop {
+                tokens: attr.tokens.clone(),
+                name: attr.name.clone(),
+                value: attr.value.clone(),
+            });
+        }
+
+        let props = PropInput {
+            krate: krate.clone(),
+            props,
+        }
+        .expand()?;
+        let style = match style {
+            Some(value) => quote_spanned! {value.span()=> #value },
+            None => quote! { #krate::tags::TagStyle::default() },
+        };
+        let handlers = if handlers.is_empty() {
+            quote! { #krate::tags::TagHandler::default() }
+        } else {
+            quote! {{
+                let mut #handlers_var = #krate::tags::TagHandler::default();
+                #(#handlers)*
+                #handlers_var
+            }}
+        };
+        // children of built in tags are checked against their content model
+        let custom = element.name.contains('-');
+        let children = self.children(&element.children, !custom)?;
+
+        if custom {
+            let name = &element.name;
+            return Ok(quote! {
+                #krate::tags::x((#name, #props, #style, #handlers, #children))
+            });
+        }
+        let span = element.tokens.span();
+        let func = Ident::new(&element.name, span);
+        if void {
+            Ok(quote! { #krate::tags::#func((#props, #style, #handlers)) })
+        } else {
+            Ok(quote! { #krate::tags::#func((#props, #style, #handlers, #children)) })
+        }
+    }
+
+    /// `<Card title="x">..</Card>` to `Card::builder().title("x").children(..).build()`,
+    /// see `#[component]`
+    fn component(&self, element: &Element) -> Result<TokenStream> {
+        let span = element.tokens.span();
+        let name = Ident::new(&element.name, span);
+        let mut setters = TokenStream::new();
+        for attr in element.attrs.iter() {
+            let setter = Ident::new(&attr.name.replace('-', "_"), attr.tokens.span());
+            let value = match &attr.value {
+                Some(value) => quote! { #value },
+                None => quote! { true },
+            };
+            setters.extend(quote! { .#setter(#value) });
+        }
+        if !element.children.is_empty() {
+            let children = self.children(&element.children, false)?;
+            let setter = Ident::new("children", span);
+            setters.extend(quote! { .#setter(#children) });
+        }
+        let build = Ident::new("build", span);
+        Ok(quote! { #name::builder() #setters.#build() })
+    }
+
+    /// `checked` children are a `ChildrenOf` whose content model is inferred from the parent tag
+    fn children(&self, nodes: &[Node], checked: bool) -> Result<TokenStream> {
+        let krate = &self.krate;
+        if nodes.is_empty() {
+            return Ok(quote! { #krate::Children(::std::vec::Vec::new()) });
+        }
+        let children = Ident::new("children", Span::mixed_site());
+        let stmts = self.stmts(&children, nodes)?;
+        let init = if checked {
+            quote! { #krate::content::ChildrenOf::new() }
+        } else {
+            quote! { #krate::Children(::std::vec::Vec::new()) }
+        };
+        Ok(quote! {{
+            let mut #children = #init;
+            #stmts
+            #children
+        }})
+    }
+
+    /// statements pushing `nodes` into `children`
+    fn stmts(&self, children: &Ident, nodes: &[Node]) -> Result<TokenStream> {
+        let mut stmts = TokenStream::new();
+        for node in nodes {
+            let stmt = match node {
+                Node::Element(element) => {
+                    let span = element.tokens.span();
+                    let element = self.element(element)?;
+                    quote_spanned! {span=> #children.push(#element); }
+                }
+                Node::Text(lit) => quote_spanned! {lit.span()=> #children.push(#lit); },
+                Node::Expr(expr) => quote_spanned! {expr.span()=> #children.append(#expr); },
+                Node::For { pat, expr, body } => {
+                    let body = self.stmts(children, body)?;
+                    quote! { for #pat in #expr { #body } }
+                }
+                Node::If {
+                    branches,
+                    otherwise,
+                } => {
+                    let mut branch_tokens = vec![];
+                    for (cond, body) in branches {
+                        let cond = &cond.0;
+                        let body = self.stmts(children, body)?;
+                        branch_tokens.push(quote! { if #cond { #body } });
+                    }
+                    let otherwise = match otherwise {
+                        Some(body) => {
+                            let body = self.stmts(children, body)?;
+                            quote! { else { #body } }
+                        }
+                        None => quote! {},
+                    };
+                    quote! { #(#branch_tokens)else* #otherwise }
+                }
+                Node::Match { expr, arms } => {
+                    let mut arm_tokens = vec![];
+                    for Arm { pat, guard, body } in arms {
+                        let guard = guard.as_ref().map(|g| quote! { if #g });
+                        let body = self.stmts(children, body)?;
+                        arm_tokens.push(quote! { #pat #guard => { #body } });
+                    }
+                    quote! { match #expr { #(#arm_tokens)* } }
+                }
+            };
+            stmts.extend(stmt);
+        }
+        Ok(stmts)
+    }
+}
@@ -1 +1,57 @@
+use proc_macro::TokenStream;
+use syn::parse_macro_input;
 
+mod component;
+mod html;
+mod prop;
+mod style;
+
+/// turn expansion errors into `compile_error!`s, wrapped in a block,
+/// so several errors are still a valid expression
+fn output(result: syn::Result<proc_macro2::TokenStream>) -> TokenStream {
+    match result {
+        Ok(tokens) => tokens.into(),
+        Err(e) => {
+            let errors = e.to_compile_error();
+            quote::quote!({ #errors }).into()
+        }
+    }
+}
+
+/// implementation of `rtml::prop!`, input is `$crate; name = value, ...`
+#[doc(hidden)]
+#[proc_macro]
+pub fn prop_impl(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as prop::PropInput);
+    output(input.expand())
+}
+
+/// implementation of `rtml::style!`, input is `$crate; name: value; ...`
+#[doc(hidden)]
+#[proc_macro]
+pub fn style_impl(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as style::StyleInput);
+    output(input.expand())
+}
+
+/// `PROPERTIES` and `property` of `rtml::style`, generated from the property table
+#[doc(hidden)]
+#[proc_macro]
+pub fn css_properties(_input: TokenStream) -> TokenStream {
+    style::properties().into()
+}
+
+/// implementation of `rtml::html!`, input is `$crate; nodes...`
+#[doc(hidden)]
+#[proc_macro]
+pub fn html_impl(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as html::HtmlInput);
+    output(input.expand())
+}
+
+/// turn a function into a reusable component, see `rtml::component`
+#[proc_macro_attribute]
+pub fn component(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let func = parse_macro_input!(item as syn::ItemFn);
+    output(component::expand(attr.into(), func))
+}
@@ -0,0 +1,161 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    Error, Expr, Ident, Lit, Result, Token,
+};
+
+/// one `name-part-... [= value]` entry
+pub struct Prop {
+    /// tokens of the name, for error spans
+    pub tokens: TokenStream,
+    pub name: String,
+    pub value: Option<Expr>,
+}
+
+/// parse an attribute name, parts joined by `-`, returns its tokens and the name
+pub fn parse_name(input: ParseStream) -> Result<(TokenStream, String)> {
+    let mut tokens = TokenStream::new();
+    let mut parts = vec![];
+    loop {
+        if input.peek(Lit) {
+            let lit: Lit = input.parse()?;
+            let part = match &lit {
+                Lit::Str(s) => s.value(),
+                Lit::Int(i) => i.to_string(),
+                _ => return Err(Error::new_spanned(lit, "expect attribute name")),
+            };
+            lit.to_tokens(&mut tokens);
+            parts.push(part);
+        } else {
+            let ident = input.call(Ident::parse_any)?;
+            ident.to_tokens(&mut tokens);
+            parts.push(ident.unraw().to_string());
+        }
+        if !input.peek(Token![-]) {
+            break;
+        }
+        input.parse::<Token![-]>()?.to_tokens(&mut tokens);
+    }
+    Ok((tokens, parts.join("-")))
+}
+
+impl Parse for Prop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let (tokens, name) = parse_name(input)?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Self {
+            tokens,
+            name,
+            value,
+        })
+    }
+}
+
+/// `$crate; props...`
+pub struct PropInput {
+    pub krate: TokenTree,
+    pub props: Vec<Prop>,
+}
+
+impl Parse for PropInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let krate = input.parse()?;
+        input.parse::<Token![;]>()?;
+        let props = input.parse_terminated::<_, Token![,]>(Prop::parse)?;
+        Ok(Self {
+            krate,
+            props: props.into_iter().collect(),
+        })
+    }
+}
+
+/// characters not allowed in an attribute name,
+/// see https://html.spec.whatwg.org/multipage/syntax.html#attributes-2
+pub fn invalid_char(c: char) -> bool {
+    c.is_whitespace()
+        || c.is_control()
+        || matches!(c, '"' | '\'' | '>' | '/' | '=' | '<' | '&' | '`')
+        || matches!(c, '\u{fdd0}'..='\u{fdef}')
+}
+
+impl Prop {
+    pub fn check(&self) -> Result<()> {
+        let error = |msg: String| Err(Error::new_spanned(&self.tokens, msg));
+        if self.name.is_empty() {
+            return error("attribute name can not be empty".to_string());
+        }
+        if let Some(c) = self.name.chars().find(|c| invalid_char(*c)) {
+            return error(format!(
+                "invalid character {:?} in attribute name {:?}",
+                c, self.name
+            ));
+        }
+        let lower = self.name.to_ascii_lowercase();
+        if lower.starts_with("on") {
+            return error(format!(
+                "event handler {} should be registered with on! macro",
+                self.name
+            ));
+        }
+        if lower == "style" {
+            return error("style property should be set with style! macro".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl PropInput {
+    pub fn expand(&self) -> Result<TokenStream> {
+        let mut errors: Option<Error> = None;
+        let mut push_error = |e: Error| match &mut errors {
+            Some(errors) => errors.combine(e),
+            None => errors = Some(e),
+        };
+        let mut seen: Vec<String> = vec![];
+        for prop in self.props.iter() {
+            if let Err(e) = prop.check() {
+                push_error(e);
+                continue;
+            }
+            // attribute names are case insensitive
+            let lower = prop.name.to_ascii_lowercase();
+            if seen.contains(&lower) {
+                push_error(Error::new_spanned(
+                    &prop.tokens,
+                    format!("duplicate attribute {}", prop.name),
+                ));
+            } else {
+                seen.push(lower);
+            }
+        }
+        if let Some(errors) = errors {
+            return Err(errors);
+        }
+
+        let krate = &self.krate;
+        if self.props.is_empty() {
+            return Ok(quote! { #krate::tags::TagProp::default() });
+        }
+        // values implementing `AttrValue` are converted with it, which knows boolean attributes,
+        // anything else only `Display`, picked by autoref, `&PropValue` is only tried after `PropValue`
+        let entries = self.props.iter().map(|prop| {
+            let name = &prop.name;
+            match &prop.value {
+                Some(value) => quote! { (#name, #krate::__private::PropValue(#value).prop_value(#name)) },
+                None => quote! { (#name, ::std::option::Option::Some(::std::string::String::new())) },
+            }
+        });
+        Ok(quote! {{
+            #[allow(unused_imports)]
+            use #krate::__private::{ViaAttrValue as _, ViaDisplay as _};
+            #krate::tags::TagProp::from_values([#(#entries),*])
+        }})
+    }
+}
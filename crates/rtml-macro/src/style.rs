@@ -0,0 +1,417 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Error, Expr, Ident, Result, Token,
+};
+
+use crate::prop::parse_name;
+
+/// vendor prefixes allowed on unknown properties
+const VENDORS: [&str; 4] = ["webkit", "moz", "ms", "o"];
+
+/// known css properties and the kind of their values, a type in `rtml::style`
+const PROPERTIES: &[(&str, &str)] = &[
+    // layout
+    ("display", "Display"),
+    ("position", "Position"),
+    ("top", "Length"),
+    ("right", "Length"),
+    ("bottom", "Length"),
+    ("left", "Length"),
+    ("inset", "Length"),
+    ("z-index", "Number"),
+    ("float", "Float"),
+    ("clear", "Clear"),
+    ("box-sizing", "BoxSizing"),
+    ("overflow", "Overflow"),
+    ("overflow-x", "Overflow"),
+    ("overflow-y", "Overflow"),
+    ("visibility", "Visibility"),
+    ("opacity", "Number"),
+    ("width", "Length"),
+    ("height", "Length"),
+    ("min-width", "Length"),
+    ("min-height", "Length"),
+    ("max-width", "Length"),
+    ("max-height", "Length"),
+    ("aspect-ratio", "Any"),
+    ("object-fit", "ObjectFit"),
+    ("object-position", "Any"),
+    ("vertical-align", "VerticalAlign"),
+    // box
+    ("margin", "Length"),
+    ("margin-top", "Length"),
+    ("margin-right", "Length"),
+    ("margin-bottom", "Length"),
+    ("margin-left", "Length"),
+    ("margin-inline", "Length"),
+    ("margin-block", "Length"),
+    ("padding", "Length"),
+    ("padding-top", "Length"),
+    ("padding-right", "Length"),
+    ("padding-bottom", "Length"),
+    ("padding-left", "Length"),
+    ("padding-inline", "Length"),
+    ("padding-block", "Length"),
+    // border
+    ("border", "Any"),
+    ("border-top", "Any"),
+    ("border-right", "Any"),
+    ("border-bottom", "Any"),
+    ("border-left", "Any"),
+    ("border-width", "Length"),
+    ("border-style", "BorderStyle"),
+    ("border-color", "Color"),
+    ("border-radius", "Length"),
+    ("border-top-left-radius", "Length"),
+    ("border-top-right-radius", "Length"),
+    ("border-bottom-left-radius", "Length"),
+    ("border-bottom-right-radius", "Length"),
+    ("border-collapse", "Any"),
+    ("border-spacing", "Length"),
+    ("outline", "Any"),
+    ("outline-width", "Length"),
+    ("outline-style", "BorderStyle"),
+    ("outline-color", "Color"),
+    ("outline-offset", "Length"),
+    ("box-shadow", "Any"),
+    // color and background
+    ("color", "Color"),
+    ("accent-color", "Color"),
+    ("caret-color", "Color"),
+    ("background", "Any"),
+    ("background-color", "Color"),
+    ("background-image", "Any"),
+    ("background-size", "Any"),
+    ("background-position", "Any"),
+    ("background-repeat", "Any"),
+    ("background-attachment", "Any"),
+    ("background-clip", "Any"),
+    ("background-origin", "Any"),
+    ("background-blend-mode", "Any"),
+    ("fill", "Any"),
+    ("stroke", "Any"),
+    ("stroke-width", "LengthOrNumber"),
+    // text
+    ("font", "Any"),
+    ("font-family", "Any"),
+    ("font-size", "Length"),
+    ("font-weight", "FontWeight"),
+    ("font-style", "FontStyle"),
+    ("font-variant", "Any"),
+    ("line-height", "LengthOrNumber"),
+    ("letter-spacing", "Length"),
+    ("word-spacing", "Length"),
+    ("text-align", "TextAlign"),
+    ("text-decoration", "TextDecoration"),
+    ("text-decoration-line", "TextDecoration"),
+    ("text-decoration-color", "Color"),
+    ("text-decoration-style", "Any"),
+    ("text-decoration-thickness", "Length"),
+    ("text-underline-offset", "Length"),
+    ("text-transform", "TextTransform"),
+    ("text-indent", "Length"),
+    ("text-overflow", "TextOverflow"),
+    ("text-shadow", "Any"),
+    ("white-space", "WhiteSpace"),
+    ("word-break", "WordBreak"),
+    ("overflow-wrap", "Any"),
+    ("hyphens", "Any"),
+    ("direction", "Any"),
+    ("writing-mode", "Any"),
+    ("tab-size", "LengthOrNumber"),
+    ("list-style", "Any"),
+    ("list-style-type", "Any"),
+    ("list-style-position", "Any"),
+    ("list-style-image", "Any"),
+    ("quotes", "Any"),
+    ("content", "Any"),
+    ("counter-reset", "Any"),
+    ("counter-increment", "Any"),
+    // flex and grid
+    ("flex", "Any"),
+    ("flex-direction", "FlexDirection"),
+    ("flex-wrap", "FlexWrap"),
+    ("flex-flow", "Any"),
+    ("flex-grow", "Number"),
+    ("flex-shrink", "Number"),
+    ("flex-basis", "Length"),
+    ("order", "Number"),
+    ("justify-content", "JustifyContent"),
+    ("justify-items", "AlignItems"),
+    ("justify-self", "AlignItems"),
+    ("align-content", "JustifyContent"),
+    ("align-items", "AlignItems"),
+    ("align-self", "AlignItems"),
+    ("place-content", "Any"),
+    ("place-items", "Any"),
+    ("place-self", "Any"),
+    ("gap", "Length"),
+    ("row-gap", "Length"),
+    ("column-gap", "Length"),
+    ("grid", "Any"),
+    ("grid-template", "Any"),
+    ("grid-template-columns", "Any"),
+    ("grid-template-rows", "Any"),
+    ("grid-template-areas", "Any"),
+    ("grid-auto-flow", "Any"),
+    ("grid-auto-columns", "Any"),
+    ("grid-auto-rows", "Any"),
+    ("grid-area", "Any"),
+    ("grid-column", "Any"),
+    ("grid-column-start", "Any"),
+    ("grid-column-end", "Any"),
+    ("grid-row", "Any"),
+    ("grid-row-start", "Any"),
+    ("grid-row-end", "Any"),
+    ("columns", "Any"),
+    ("column-count", "Number"),
+    ("column-width", "Length"),
+    ("column-rule", "Any"),
+    // table
+    ("table-layout", "Any"),
+    ("caption-side", "Any"),
+    ("empty-cells", "Any"),
+    // transform and animation
+    ("transform", "Any"),
+    ("transform-origin", "Any"),
+    ("translate", "Any"),
+    ("rotate", "Any"),
+    ("scale", "Any"),
+    ("perspective", "Length"),
+    ("transition", "Any"),
+    ("transition-property", "Any"),
+    ("transition-duration", "Any"),
+    ("transition-timing-function", "Any"),
+    ("transition-delay", "Any"),
+    ("animation", "Any"),
+    ("animation-name", "Any"),
+    ("animation-duration", "Any"),
+    ("animation-timing-function", "Any"),
+    ("animation-delay", "Any"),
+    ("animation-iteration-count", "Any"),
+    ("animation-direction", "Any"),
+    ("animation-fill-mode", "Any"),
+    ("animation-play-state", "Any"),
+    ("will-change", "Any"),
+    // interaction
+    ("cursor", "Cursor"),
+    ("pointer-events", "PointerEvents"),
+    ("user-select", "UserSelect"),
+    ("resize", "Any"),
+    ("scroll-behavior", "Any"),
+    ("touch-action", "Any"),
+    ("appearance", "Any"),
+    // effects
+    ("filter", "Any"),
+    ("backdrop-filter", "Any"),
+    ("clip-path", "Any"),
+    ("mask", "Any"),
+    ("mix-blend-mode", "Any"),
+    ("isolation", "Any"),
+    ("contain", "Any"),
+    ("content-visibility", "Any"),
+    ("all", "Any"),
+];
+
+/// edit distance between two names, to suggest a known property for a typo
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                prev
+            } else {
+                prev.min(row[j]).min(current) + 1
+            };
+            prev = current;
+        }
+    }
+    row[b.len()]
+}
+
+/// `PROPERTIES` names and one function per property in `property`,
+/// taking the values of its kind, expanded inside `rtml::style`
+pub fn properties() -> TokenStream {
+    let names = PROPERTIES.iter().map(|(name, _)| name);
+    let funcs = PROPERTIES.iter().map(|(name, kind)| {
+        let func = Ident::new(&name.replace('-', "_"), Span::call_site());
+        let kind = Ident::new(kind, Span::call_site());
+        let doc = format!("`{}`", name);
+        quote! {
+            #[doc = #doc]
+            pub fn #func<V: CssValue<#kind>>(value: V) -> (&'static str, String) {
+                (#name, value.to_css())
+            }
+        }
+    });
+    quote! {
+        /// names of known css properties, [`style!`](crate::style) rejects other names
+        /// unless they are custom or vendor prefixed
+        pub const PROPERTIES: &[&str] = &[#(#names),*];
+
+        /// one function per known property, turning a value of its kind into a declaration,
+        /// [`style!`](crate::style) calls them to check values at compile time
+        pub mod property {
+            use super::*;
+
+            #(#funcs)*
+        }
+    }
+}
+
+/// one `name: value` declaration
+pub struct Declaration {
+    /// tokens of the name, for error spans
+    pub tokens: TokenStream,
+    /// leading `-` of custom or vendor prefixed properties
+    pub dashes: usize,
+    pub name: String,
+    pub value: Expr,
+}
+
+impl Parse for Declaration {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut tokens = TokenStream::new();
+        let mut dashes = 0;
+        while input.peek(Token![-]) {
+            input.parse::<Token![-]>()?.to_tokens(&mut tokens);
+            dashes += 1;
+        }
+        let (name_tokens, name) = parse_name(input)?;
+        tokens.extend(name_tokens);
+        input.parse::<Token![:]>()?;
+        let value = input.parse()?;
+        Ok(Self {
+            tokens,
+            dashes,
+            name,
+            value,
+        })
+    }
+}
+
+impl Declaration {
+    /// full property name, with leading dashes
+    fn full_name(&self) -> String {
+        format!("{}{}", "-".repeat(self.dashes), self.name)
+    }
+
+    fn check(&self) -> Result<()> {
+        let vendor = self.name.split('-').next().unwrap_or_default();
+        match self.dashes {
+            0 if PROPERTIES.iter().any(|(name, _)| *name == self.name) => Ok(()),
+            0 => {
+                let similar = PROPERTIES
+                    .iter()
+                    .map(|(name, _)| (distance(name, &self.name), name))
+                    .filter(|(d, _)| *d <= 2)
+                    .min();
+                let msg = match similar {
+                    Some((_, name)) => {
+                        format!("unknown property {}, did you mean {}?", self.name, name)
+                    }
+                    None => format!(
+                        "unknown property {}, prefix it with -- for a custom property",
+                        self.name
+                    ),
+                };
+                Err(Error::new_spanned(&self.tokens, msg))
+            }
+            2 => Ok(()),
+            1 if VENDORS.contains(&vendor) && self.name.len() > vendor.len() => Ok(()),
+            _ => Err(Error::new_spanned(
+                &self.tokens,
+                format!(
+                    "unknown property {}, expect a css property, a custom property starting \
+                     with -- or a vendor prefix -webkit-, -moz-, -ms- or -o-",
+                    self.full_name()
+                ),
+            )),
+        }
+    }
+
+    /// `(name, value)` of the declaration, known properties go through the function
+    /// of the same name in `style::property`, so a value of the wrong kind is reported
+    /// at the value
+    fn expand(&self, krate: &TokenTree) -> TokenStream {
+        let value = &self.value;
+        if self.dashes > 0 {
+            let name = self.full_name();
+            return quote! {
+                (#name, #krate::style::CssValue::<#krate::style::Any>::to_css(#value))
+            };
+        }
+        let func = Ident::new(&self.name.replace('-', "_"), Span::call_site());
+        quote::quote_spanned!(value.span()=> #krate::style::property::#func(#value))
+    }
+}
+
+/// `$crate; declarations...`
+pub struct StyleInput {
+    pub krate: TokenTree,
+    pub declarations: Vec<Declaration>,
+}
+
+impl Parse for StyleInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let krate = input.parse()?;
+        input.parse::<Token![;]>()?;
+        let declarations = input.parse_terminated::<_, Token![;]>(Declaration::parse)?;
+        Ok(Self {
+            krate,
+            declarations: declarations.into_iter().collect(),
+        })
+    }
+}
+
+impl StyleInput {
+    pub fn expand(&self) -> Result<TokenStream> {
+        let mut errors: Option<Error> = None;
+        let mut push_error = |e: Error| match &mut errors {
+            Some(errors) => errors.combine(e),
+            None => errors = Some(e),
+        };
+        let mut seen: Vec<String> = vec![];
+        for decl in self.declarations.iter() {
+            if let Err(e) = decl.check() {
+                push_error(e);
+                continue;
+            }
+            let name = decl.full_name();
+            if seen.contains(&name) {
+                push_error(Error::new_spanned(
+                    &decl.tokens,
+                    format!("duplicate property {}", name),
+                ));
+            } else {
+                seen.push(name);
+            }
+        }
+        if let Some(errors) = errors {
+            return Err(errors);
+        }
+
+        let krate = &self.krate;
+        if self.declarations.is_empty() {
+            return Ok(quote! { #krate::tags::TagStyle::default() });
+        }
+        let entries = self.declarations.iter().map(|decl| decl.expand(krate));
+        Ok(quote! {
+            {
+                let mut map = #krate::tags::OrderedMap::new();
+                #(
+                    let (name, value) = #entries;
+                    map.insert(name, value);
+                )*
+                #krate::tags::TagStyle(map, Vec::new())
+            }
+        })
+    }
+}
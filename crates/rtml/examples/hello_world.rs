@@ -1,6 +1,6 @@
 use rtml::tags::*;
 
 fn main() {
-    let page = html(h1("hello world!"));
+    let page = html(body(h1("hello world!")));
     println!("{}", page);
 }
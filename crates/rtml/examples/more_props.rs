@@ -6,7 +6,7 @@ fn main() {
     let page = html((
         prop! { lang = "zh-cn" },
         (
-            header((
+            head((
                 meta(prop! { charset = "utf-8" }),
                 title("html file generated by Rust!"),
             )),
@@ -0,0 +1,1202 @@
+//! typed attribute setters of built in tags, enumerated attributes take enums,
+//! numeric ones take numbers and boolean ones take `bool`
+//!
+//! ```
+//! use rtml::{attrs::*, tags::*};
+//!
+//! let page = form((
+//!     input(()).r#type(InputType::Email).name("mail").required(true),
+//!     img(()).src("logo.png").alt("logo").width(64u32),
+//! ))
+//! .method(Method::Post)
+//! .action("/subscribe");
+//! let html = page.to_string();
+//! assert!(html.contains(r#"<form method="post" action="/subscribe">"#));
+//! assert!(html.contains(r#"<input type="email" name="mail" required>"#));
+//! assert!(html.contains(r#"<img src="logo.png" alt="logo" width="64">"#));
+//! ```
+//!
+//! setters also take an `Option`, `None` leaves the attribute out
+//!
+//! ```
+//! use rtml::tags::*;
+//!
+//! let caption: Option<String> = None;
+//! let image = img(()).src("a.png").alt(caption).ismap(false);
+//! assert_eq!(image.to_string().trim_end(), r#"<img src="a.png">"#);
+//! ```
+//!
+//! attribute names are checked, [`prop!`](crate::prop) stays available for anything else
+//!
+//! ```compile_fail
+//! use rtml::tags::*;
+//! let link = a("home").hreff("/");
+//! ```
+
+use std::{borrow::Cow, fmt::Display};
+
+use crate::{format::is_boolean_attribute, tags::*};
+
+/// value of an attribute, `None` removes it, `Some("")` is written as a bare attribute for boolean ones
+pub trait AttrValue {
+    fn into_attr(self) -> Option<String>;
+
+    /// value for attribute `name`, only differs from [`into_attr`](AttrValue::into_attr) for `bool`
+    fn into_attr_named(self, _name: &str) -> Option<String>
+    where
+        Self: Sized,
+    {
+        self.into_attr()
+    }
+}
+
+/// the attribute is left out if `None`
+impl<T: AttrValue> AttrValue for Option<T> {
+    fn into_attr(self) -> Option<String> {
+        self.and_then(AttrValue::into_attr)
+    }
+
+    fn into_attr_named(self, name: &str) -> Option<String> {
+        self.and_then(|value| value.into_attr_named(name))
+    }
+}
+
+impl<T: AttrValue + Clone> AttrValue for &T {
+    fn into_attr(self) -> Option<String> {
+        self.clone().into_attr()
+    }
+
+    fn into_attr_named(self, name: &str) -> Option<String> {
+        self.clone().into_attr_named(name)
+    }
+}
+
+/// present or absent, attributes which are not boolean attributes, such as `draggable`
+/// or `aria-hidden`, get `"true"` or `"false"` when named
+///
+/// ```
+/// use rtml::attrs::AttrValue;
+///
+/// assert_eq!(false.into_attr_named("disabled"), None);
+/// assert_eq!(false.into_attr_named("draggable").as_deref(), Some("false"));
+/// ```
+impl AttrValue for bool {
+    fn into_attr(self) -> Option<String> {
+        self.then(String::new)
+    }
+
+    fn into_attr_named(self, name: &str) -> Option<String> {
+        if is_boolean_attribute(name) {
+            self.into_attr()
+        } else {
+            Some(self.to_string())
+        }
+    }
+}
+
+/// `prop!` value, converted with [`AttrValue`] if it implements it, or `Display` otherwise
+#[doc(hidden)]
+pub struct PropValue<T>(pub T);
+
+#[doc(hidden)]
+pub trait ViaAttrValue {
+    fn prop_value(self, name: &str) -> Option<String>;
+}
+
+impl<T: AttrValue> ViaAttrValue for PropValue<T> {
+    fn prop_value(self, name: &str) -> Option<String> {
+        self.0.into_attr_named(name)
+    }
+}
+
+#[doc(hidden)]
+pub trait ViaDisplay {
+    fn prop_value(self, name: &str) -> Option<String>;
+}
+
+impl<T: Display> ViaDisplay for &PropValue<T> {
+    fn prop_value(self, _name: &str) -> Option<String> {
+        Some(self.0.to_string())
+    }
+}
+
+impl AttrValue for String {
+    fn into_attr(self) -> Option<String> {
+        Some(self)
+    }
+}
+
+impl AttrValue for &str {
+    fn into_attr(self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl AttrValue for Cow<'_, str> {
+    fn into_attr(self) -> Option<String> {
+        Some(self.into_owned())
+    }
+}
+
+impl AttrValue for char {
+    fn into_attr(self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+/// argument of typed setters, a value or an `Option` of it, which removes the attribute if `None`
+pub trait IntoOptional<T> {
+    fn into_optional(self) -> Option<T>;
+}
+
+impl<T> IntoOptional<T> for T {
+    fn into_optional(self) -> Option<T> {
+        Some(self)
+    }
+}
+
+impl<T> IntoOptional<T> for Option<T> {
+    fn into_optional(self) -> Option<T> {
+        self
+    }
+}
+
+impl IntoOptional<String> for &str {
+    fn into_optional(self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl IntoOptional<String> for Option<&str> {
+    fn into_optional(self) -> Option<String> {
+        self.map(ToString::to_string)
+    }
+}
+
+macro_rules! number_value {
+    ($($t:ty),+) => {
+        $(
+            impl AttrValue for $t {
+                fn into_attr(self) -> Option<String> {
+                    Some(self.to_string())
+                }
+            }
+        )+
+    };
+}
+
+number_value!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+/// define an enumerated attribute value
+macro_rules! attr_enum {
+    ($(#[$doc:meta])* $name:ident { $($variant:ident = $value:literal),+ $(,)? }) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $(
+                #[doc = concat!("`", $value, "`")]
+                $variant,
+            )+
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $value,)+
+                }
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl AttrValue for $name {
+            fn into_attr(self) -> Option<String> {
+                Some(self.as_str().to_string())
+            }
+        }
+    };
+}
+
+attr_enum!(
+    /// `type` of `input`
+    InputType {
+        Button = "button",
+        Checkbox = "checkbox",
+        Color = "color",
+        Date = "date",
+        DatetimeLocal = "datetime-local",
+        Email = "email",
+        File = "file",
+        Hidden = "hidden",
+        Image = "image",
+        Month = "month",
+        Number = "number",
+        Password = "password",
+        Radio = "radio",
+        Range = "range",
+        Reset = "reset",
+        Search = "search",
+        Submit = "submit",
+        Tel = "tel",
+        Text = "text",
+        Time = "time",
+        Url = "url",
+        Week = "week",
+    }
+);
+
+attr_enum!(
+    /// `type` of `button`
+    ButtonType {
+        Submit = "submit",
+        Reset = "reset",
+        Button = "button",
+    }
+);
+
+attr_enum!(
+    /// `method` of `form`, `formmethod` of `button` and `input`
+    Method {
+        Get = "get",
+        Post = "post",
+        Dialog = "dialog",
+    }
+);
+
+attr_enum!(
+    /// `enctype` of `form`, `formenctype` of `button` and `input`
+    Enctype {
+        UrlEncoded = "application/x-www-form-urlencoded",
+        Multipart = "multipart/form-data",
+        Plain = "text/plain",
+    }
+);
+
+attr_enum!(
+    /// browsing context of `target` and `formtarget`, use [`prop!`](crate::prop) for named ones
+    Target {
+        Self_ = "_self",
+        Blank = "_blank",
+        Parent = "_parent",
+        Top = "_top",
+    }
+);
+
+attr_enum!(
+    /// `loading` of `img` and `iframe`
+    Loading {
+        Eager = "eager",
+        Lazy = "lazy",
+    }
+);
+
+attr_enum!(
+    /// `decoding` of `img`
+    Decoding {
+        Sync = "sync",
+        Async = "async",
+        Auto = "auto",
+    }
+);
+
+attr_enum!(
+    /// `fetchpriority` of `img`, `link` and `script`
+    FetchPriority {
+        High = "high",
+        Low = "low",
+        Auto = "auto",
+    }
+);
+
+attr_enum!(
+    /// `crossorigin` of media, `link` and `script`
+    CrossOrigin {
+        Anonymous = "anonymous",
+        UseCredentials = "use-credentials",
+    }
+);
+
+attr_enum!(
+    /// `referrerpolicy`
+    ReferrerPolicy {
+        NoReferrer = "no-referrer",
+        NoReferrerWhenDowngrade = "no-referrer-when-downgrade",
+        Origin = "origin",
+        OriginWhenCrossOrigin = "origin-when-cross-origin",
+        SameOrigin = "same-origin",
+        StrictOrigin = "strict-origin",
+        StrictOriginWhenCrossOrigin = "strict-origin-when-cross-origin",
+        UnsafeUrl = "unsafe-url",
+    }
+);
+
+attr_enum!(
+    /// `preload` of `audio` and `video`
+    Preload {
+        None = "none",
+        Metadata = "metadata",
+        Auto = "auto",
+    }
+);
+
+attr_enum!(
+    /// `type` of `ol`
+    ListType {
+        Decimal = "1",
+        LowerAlpha = "a",
+        UpperAlpha = "A",
+        LowerRoman = "i",
+        UpperRoman = "I",
+    }
+);
+
+attr_enum!(
+    /// `wrap` of `textarea`
+    Wrap {
+        Hard = "hard",
+        Soft = "soft",
+        Off = "off",
+    }
+);
+
+attr_enum!(
+    /// `scope` of `th`
+    Scope {
+        Row = "row",
+        Col = "col",
+        RowGroup = "rowgroup",
+        ColGroup = "colgroup",
+    }
+);
+
+attr_enum!(
+    /// `kind` of `track`
+    TrackKind {
+        Subtitles = "subtitles",
+        Captions = "captions",
+        Descriptions = "descriptions",
+        Chapters = "chapters",
+        Metadata = "metadata",
+    }
+);
+
+attr_enum!(
+    /// `shape` of `area`
+    Shape {
+        Rect = "rect",
+        Circle = "circle",
+        Poly = "poly",
+        Default = "default",
+    }
+);
+
+attr_enum!(
+    /// `popovertargetaction` of `button` and `input`
+    PopoverAction {
+        Toggle = "toggle",
+        Show = "show",
+        Hide = "hide",
+    }
+);
+
+/// implement typed setters, the attribute name is the method name unless given
+macro_rules! attrs {
+    ($($struct:ident { $($method:ident $(= $attr:literal)?: $ty:ty),* $(,)? })+) => {
+        $(
+            impl $struct {
+                $(
+                    #[doc = concat!("set `", attrs!(@name $method $($attr)?), "`")]
+                    pub fn $method(self, value: impl IntoOptional<$ty>) -> Self {
+                        self.attr(attrs!(@name $method $($attr)?), value.into_optional())
+                    }
+                )*
+            }
+        )+
+    };
+    (@name $method:ident $attr:literal) => {
+        $attr
+    };
+    (@name $method:ident) => {
+        stringify!($method)
+    };
+}
+
+attrs! {
+    A {
+        href: String,
+        target: Target,
+        download: String,
+        ping: String,
+        rel: String,
+        hreflang: String,
+        r#type = "type": String,
+        referrerpolicy: ReferrerPolicy,
+    }
+    Area {
+        alt: String,
+        coords: String,
+        shape: Shape,
+        href: String,
+        target: Target,
+        download: String,
+        ping: String,
+        rel: String,
+        referrerpolicy: ReferrerPolicy,
+    }
+    Audio {
+        src: String,
+        crossorigin: CrossOrigin,
+        preload: Preload,
+        autoplay: bool,
+        r#loop = "loop": bool,
+        muted: bool,
+        controls: bool,
+    }
+    Video {
+        src: String,
+        crossorigin: CrossOrigin,
+        preload: Preload,
+        autoplay: bool,
+        r#loop = "loop": bool,
+        muted: bool,
+        controls: bool,
+        poster: String,
+        playsinline: bool,
+        width: u32,
+        height: u32,
+    }
+    Base {
+        href: String,
+        target: Target,
+    }
+    Blockquote {
+        cite: String,
+    }
+    Q {
+        cite: String,
+    }
+    Del {
+        cite: String,
+        datetime: String,
+    }
+    Ins {
+        cite: String,
+        datetime: String,
+    }
+    Button {
+        disabled: bool,
+        form: String,
+        formaction: String,
+        formenctype: Enctype,
+        formmethod: Method,
+        formnovalidate: bool,
+        formtarget: Target,
+        name: String,
+        popovertarget: String,
+        popovertargetaction: PopoverAction,
+        r#type = "type": ButtonType,
+        value: String,
+    }
+    Canvas {
+        width: u32,
+        height: u32,
+    }
+    Col {
+        span: u32,
+    }
+    Colgroup {
+        span: u32,
+    }
+    Data {
+        value: String,
+    }
+    Details {
+        open: bool,
+        name: String,
+    }
+    Dialog {
+        open: bool,
+    }
+    Embed {
+        src: String,
+        r#type = "type": String,
+        width: u32,
+        height: u32,
+    }
+    Fieldset {
+        disabled: bool,
+        form: String,
+        name: String,
+    }
+    Form {
+        accept_charset = "accept-charset": String,
+        action: String,
+        autocomplete: String,
+        enctype: Enctype,
+        method: Method,
+        name: String,
+        novalidate: bool,
+        target: Target,
+        rel: String,
+    }
+    Iframe {
+        src: String,
+        srcdoc: String,
+        name: String,
+        sandbox: String,
+        allow: String,
+        allowfullscreen: bool,
+        width: u32,
+        height: u32,
+        referrerpolicy: ReferrerPolicy,
+        loading: Loading,
+    }
+    Img {
+        alt: String,
+        src: String,
+        srcset: String,
+        sizes: String,
+        crossorigin: CrossOrigin,
+        usemap: String,
+        ismap: bool,
+        width: u32,
+        height: u32,
+        referrerpolicy: ReferrerPolicy,
+        decoding: Decoding,
+        loading: Loading,
+        fetchpriority: FetchPriority,
+    }
+    Input {
+        accept: String,
+        alt: String,
+        autocomplete: String,
+        checked: bool,
+        dirname: String,
+        disabled: bool,
+        form: String,
+        formaction: String,
+        formenctype: Enctype,
+        formmethod: Method,
+        formnovalidate: bool,
+        formtarget: Target,
+        height: u32,
+        list: String,
+        max: String,
+        maxlength: u32,
+        min: String,
+        minlength: u32,
+        multiple: bool,
+        name: String,
+        pattern: String,
+        placeholder: String,
+        popovertarget: String,
+        popovertargetaction: PopoverAction,
+        readonly: bool,
+        required: bool,
+        size: u32,
+        src: String,
+        step: String,
+        r#type = "type": InputType,
+        value: String,
+        width: u32,
+    }
+    Label {
+        r#for = "for": String,
+    }
+    Li {
+        value: i64,
+    }
+    Link {
+        href: String,
+        crossorigin: CrossOrigin,
+        rel: String,
+        media: String,
+        integrity: String,
+        hreflang: String,
+        r#type = "type": String,
+        referrerpolicy: ReferrerPolicy,
+        sizes: String,
+        r#as = "as": String,
+        disabled: bool,
+        fetchpriority: FetchPriority,
+    }
+    Map {
+        name: String,
+    }
+    Meta {
+        name: String,
+        http_equiv = "http-equiv": String,
+        content: String,
+        charset: String,
+        media: String,
+    }
+    Meter {
+        value: f64,
+        min: f64,
+        max: f64,
+        low: f64,
+        high: f64,
+        optimum: f64,
+    }
+    Object {
+        data: String,
+        r#type = "type": String,
+        name: String,
+        form: String,
+        width: u32,
+        height: u32,
+    }
+    Ol {
+        reversed: bool,
+        start: i64,
+        r#type = "type": ListType,
+    }
+    Optgroup {
+        disabled: bool,
+        label: String,
+    }
+    Option_ {
+        disabled: bool,
+        label: String,
+        selected: bool,
+        value: String,
+    }
+    Output {
+        r#for = "for": String,
+        form: String,
+        name: String,
+    }
+    Progress {
+        value: f64,
+        max: f64,
+    }
+    Script {
+        src: String,
+        r#type = "type": String,
+        nomodule: bool,
+        r#async = "async": bool,
+        defer: bool,
+        crossorigin: CrossOrigin,
+        integrity: String,
+        referrerpolicy: ReferrerPolicy,
+        fetchpriority: FetchPriority,
+    }
+    Select {
+        autocomplete: String,
+        disabled: bool,
+        form: String,
+        multiple: bool,
+        name: String,
+        required: bool,
+        size: u32,
+    }
+    Slot {
+        name: String,
+    }
+    Source {
+        r#type = "type": String,
+        media: String,
+        src: String,
+        srcset: String,
+        sizes: String,
+        width: u32,
+        height: u32,
+    }
+    Style {
+        media: String,
+    }
+    Td {
+        colspan: u32,
+        rowspan: u32,
+        headers: String,
+    }
+    Th {
+        colspan: u32,
+        rowspan: u32,
+        headers: String,
+        scope: Scope,
+        abbr: String,
+    }
+    Textarea {
+        autocomplete: String,
+        cols: u32,
+        dirname: String,
+        disabled: bool,
+        form: String,
+        maxlength: u32,
+        minlength: u32,
+        name: String,
+        placeholder: String,
+        readonly: bool,
+        required: bool,
+        rows: u32,
+        wrap: Wrap,
+    }
+    Time {
+        datetime: String,
+    }
+    Track {
+        default: bool,
+        kind: TrackKind,
+        label: String,
+        src: String,
+        srclang: String,
+    }
+}
+
+/// space separated classes, without duplicates, in insertion order
+///
+/// ```
+/// use rtml::{attrs::ClassList, tags::*};
+///
+/// let mut classes = ClassList::from("btn btn-primary");
+/// classes.toggle("active");
+/// classes.remove("btn-primary");
+/// let button = button("ok").class(classes).class(["large", "btn"]);
+/// assert_eq!(button.to_string().trim_end(), r#"<button class="btn active large">ok</button>"#);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClassList(Vec<String>);
+
+impl ClassList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// add a class, ignored if it is already there
+    pub fn add(&mut self, class: &str) {
+        if !self.contains(class) {
+            self.0.push(class.to_string());
+        }
+    }
+
+    pub fn remove(&mut self, class: &str) {
+        self.0.retain(|c| c != class);
+    }
+
+    /// remove the class if it is there, add it otherwise, return whether it is there now
+    pub fn toggle(&mut self, class: &str) -> bool {
+        if self.contains(class) {
+            self.remove(class);
+            false
+        } else {
+            self.add(class);
+            true
+        }
+    }
+
+    pub fn contains(&self, class: &str) -> bool {
+        self.0.iter().any(|c| c == class)
+    }
+
+    /// add every class of `other`
+    pub fn merge(&mut self, other: ClassList) {
+        self.extend(other.0);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for ClassList {
+    fn extend<T: IntoIterator<Item = S>>(&mut self, iter: T) {
+        for class in iter {
+            for class in class.as_ref().split_ascii_whitespace() {
+                self.add(class);
+            }
+        }
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for ClassList {
+    fn from_iter<T: IntoIterator<Item = S>>(iter: T) -> Self {
+        let mut classes = Self::new();
+        classes.extend(iter);
+        classes
+    }
+}
+
+impl From<&str> for ClassList {
+    fn from(classes: &str) -> Self {
+        Self::from_iter([classes])
+    }
+}
+
+impl From<String> for ClassList {
+    fn from(classes: String) -> Self {
+        Self::from_iter([classes])
+    }
+}
+
+impl<S: AsRef<str>, const N: usize> From<[S; N]> for ClassList {
+    fn from(classes: [S; N]) -> Self {
+        Self::from_iter(classes)
+    }
+}
+
+impl<S: AsRef<str>> From<Vec<S>> for ClassList {
+    fn from(classes: Vec<S>) -> Self {
+        Self::from_iter(classes)
+    }
+}
+
+impl std::ops::Add for ClassList {
+    type Output = ClassList;
+
+    fn add(mut self, other: ClassList) -> ClassList {
+        self.merge(other);
+        self
+    }
+}
+
+impl Display for ClassList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0.join(" "))
+    }
+}
+
+/// omitted when empty
+impl AttrValue for ClassList {
+    fn into_attr(self) -> Option<String> {
+        (!self.is_empty()).then(|| self.to_string())
+    }
+}
+
+/// whether `key` can be used in `data-{key}`: not empty, no ascii uppercase,
+/// no whitespace, quotes, `<`, `>`, `/`, `=` or control characters, and not starting with `xml`
+pub fn is_valid_data_key(key: &str) -> bool {
+    !key.is_empty()
+        && !key.to_ascii_lowercase().starts_with("xml")
+        && !key.chars().any(|c| {
+            c.is_ascii_uppercase()
+                || c.is_whitespace()
+                || c.is_control()
+                || matches!(c, '"' | '\'' | '<' | '>' | '/' | '=')
+        })
+}
+
+/// whether `name` can be written as an attribute name, the same rule `prop!` checks:
+/// not empty, no whitespace, quotes, `<`, `>`, `/`, `=`, `&`, `` ` ``, control characters or noncharacters
+///
+/// ```
+/// use rtml::attrs::is_valid_attr_name;
+///
+/// assert!(is_valid_attr_name("aria-label"));
+/// assert!(is_valid_attr_name("@click"));
+/// assert!(!is_valid_attr_name(r#"x"><script>"#));
+/// ```
+pub fn is_valid_attr_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.chars().any(|c| {
+            c.is_whitespace()
+                || c.is_control()
+                || matches!(c, '"' | '\'' | '>' | '/' | '=' | '<' | '&' | '`')
+                || matches!(c, '\u{fdd0}'..='\u{fdef}')
+        })
+}
+
+/// key passed to [`GlobalAttrs::data`] which is not valid, see [`is_valid_data_key`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidDataKey(pub String);
+
+impl Display for InvalidDataKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid data attribute name data-{}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidDataKey {}
+
+attr_enum!(
+    /// `role`, non abstract roles of WAI-ARIA 1.2
+    Role {
+        Alert = "alert",
+        AlertDialog = "alertdialog",
+        Application = "application",
+        Article = "article",
+        Banner = "banner",
+        Blockquote = "blockquote",
+        Button = "button",
+        Caption = "caption",
+        Cell = "cell",
+        Checkbox = "checkbox",
+        Code = "code",
+        ColumnHeader = "columnheader",
+        Combobox = "combobox",
+        Complementary = "complementary",
+        ContentInfo = "contentinfo",
+        Definition = "definition",
+        Deletion = "deletion",
+        Dialog = "dialog",
+        Document = "document",
+        Emphasis = "emphasis",
+        Feed = "feed",
+        Figure = "figure",
+        Form = "form",
+        Generic = "generic",
+        Grid = "grid",
+        GridCell = "gridcell",
+        Group = "group",
+        Heading = "heading",
+        Img = "img",
+        Insertion = "insertion",
+        Link = "link",
+        List = "list",
+        Listbox = "listbox",
+        ListItem = "listitem",
+        Log = "log",
+        Main = "main",
+        Marquee = "marquee",
+        Math = "math",
+        Menu = "menu",
+        Menubar = "menubar",
+        MenuItem = "menuitem",
+        MenuItemCheckbox = "menuitemcheckbox",
+        MenuItemRadio = "menuitemradio",
+        Meter = "meter",
+        Navigation = "navigation",
+        None = "none",
+        Note = "note",
+        Option = "option",
+        Paragraph = "paragraph",
+        Presentation = "presentation",
+        ProgressBar = "progressbar",
+        Radio = "radio",
+        RadioGroup = "radiogroup",
+        Region = "region",
+        Row = "row",
+        RowGroup = "rowgroup",
+        RowHeader = "rowheader",
+        Scrollbar = "scrollbar",
+        Search = "search",
+        Searchbox = "searchbox",
+        Separator = "separator",
+        Slider = "slider",
+        SpinButton = "spinbutton",
+        Status = "status",
+        Strong = "strong",
+        Subscript = "subscript",
+        Superscript = "superscript",
+        Switch = "switch",
+        Tab = "tab",
+        Table = "table",
+        TabList = "tablist",
+        TabPanel = "tabpanel",
+        Term = "term",
+        Textbox = "textbox",
+        Time = "time",
+        Timer = "timer",
+        Toolbar = "toolbar",
+        Tooltip = "tooltip",
+        Tree = "tree",
+        TreeGrid = "treegrid",
+        TreeItem = "treeitem",
+    }
+);
+
+attr_enum!(
+    /// `aria-checked` and `aria-pressed`
+    Tristate {
+        True = "true",
+        False = "false",
+        Mixed = "mixed",
+    }
+);
+
+attr_enum!(
+    /// `aria-current`
+    AriaCurrent {
+        Page = "page",
+        Step = "step",
+        Location = "location",
+        Date = "date",
+        Time = "time",
+        True = "true",
+        False = "false",
+    }
+);
+
+attr_enum!(
+    /// `aria-live`
+    Live {
+        Off = "off",
+        Polite = "polite",
+        Assertive = "assertive",
+    }
+);
+
+attr_enum!(
+    /// `aria-haspopup`
+    HasPopup {
+        False = "false",
+        True = "true",
+        Menu = "menu",
+        Listbox = "listbox",
+        Tree = "tree",
+        Grid = "grid",
+        Dialog = "dialog",
+    }
+);
+
+/// typed `aria-*` states and properties, booleans are written as `"true"` and `"false"`,
+/// set the others with [`prop!`](crate::prop)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Aria {
+    Label(String),
+    LabelledBy(String),
+    DescribedBy(String),
+    Controls(String),
+    Owns(String),
+    Hidden(bool),
+    Expanded(bool),
+    Selected(bool),
+    Disabled(bool),
+    Busy(bool),
+    Invalid(bool),
+    Required(bool),
+    Modal(bool),
+    Atomic(bool),
+    Checked(Tristate),
+    Pressed(Tristate),
+    Current(AriaCurrent),
+    Live(Live),
+    HasPopup(HasPopup),
+    Level(u32),
+    ValueNow(f64),
+    ValueMin(f64),
+    ValueMax(f64),
+    ValueText(String),
+}
+
+impl Aria {
+    /// attribute name and value
+    pub fn pair(self) -> (&'static str, String) {
+        match self {
+            Self::Label(v) => ("aria-label", v),
+            Self::LabelledBy(v) => ("aria-labelledby", v),
+            Self::DescribedBy(v) => ("aria-describedby", v),
+            Self::Controls(v) => ("aria-controls", v),
+            Self::Owns(v) => ("aria-owns", v),
+            Self::Hidden(v) => ("aria-hidden", v.to_string()),
+            Self::Expanded(v) => ("aria-expanded", v.to_string()),
+            Self::Selected(v) => ("aria-selected", v.to_string()),
+            Self::Disabled(v) => ("aria-disabled", v.to_string()),
+            Self::Busy(v) => ("aria-busy", v.to_string()),
+            Self::Invalid(v) => ("aria-invalid", v.to_string()),
+            Self::Required(v) => ("aria-required", v.to_string()),
+            Self::Modal(v) => ("aria-modal", v.to_string()),
+            Self::Atomic(v) => ("aria-atomic", v.to_string()),
+            Self::Checked(v) => ("aria-checked", v.to_string()),
+            Self::Pressed(v) => ("aria-pressed", v.to_string()),
+            Self::Current(v) => ("aria-current", v.to_string()),
+            Self::Live(v) => ("aria-live", v.to_string()),
+            Self::HasPopup(v) => ("aria-haspopup", v.to_string()),
+            Self::Level(v) => ("aria-level", v.to_string()),
+            Self::ValueNow(v) => ("aria-valuenow", v.to_string()),
+            Self::ValueMin(v) => ("aria-valuemin", v.to_string()),
+            Self::ValueMax(v) => ("aria-valuemax", v.to_string()),
+            Self::ValueText(v) => ("aria-valuetext", v),
+        }
+    }
+}
+
+/// global attributes, implemented by [`UnitTag`] and every built in tag,
+/// `data` of `object` is its own attribute, call `GlobalAttrs::data` for `data-*` there
+///
+/// ```
+/// use rtml::{attrs::*, tags::*};
+///
+/// let menu = ul(li("home"))
+///     .id("menu")
+///     .class("nav")
+///     .role(Role::Menu)
+///     .aria(Aria::Expanded(false))
+///     .data("user-id", 42)
+///     .unwrap();
+/// assert_eq!(
+///     menu.to_string().trim_end(),
+///     r#"<ul id="menu" class="nav" role="menu" aria-expanded="false" data-user-id="42"><li>home</li></ul>"#
+/// );
+/// assert!(ul(()).data("x><script", 1).is_err());
+/// ```
+pub trait GlobalAttrs: Sized {
+    /// attributes of the tag
+    fn props(&self) -> &TagProp;
+
+    fn props_mut(&mut self) -> &mut TagProp;
+
+    fn id(mut self, id: impl IntoOptional<String>) -> Self {
+        self.props_mut().set("id", id.into_optional());
+        self
+    }
+
+    /// current classes
+    fn class_list(&self) -> ClassList {
+        self.props()
+            .0
+            .get("class")
+            .map(|classes| ClassList::from(classes.as_str()))
+            .unwrap_or_default()
+    }
+
+    /// add classes, merged with the ones already set
+    fn class(mut self, classes: impl Into<ClassList>) -> Self {
+        let merged = self.class_list() + classes.into();
+        self.props_mut().set("class", merged.into_attr());
+        self
+    }
+
+    fn remove_class(mut self, class: &str) -> Self {
+        let mut classes = self.class_list();
+        classes.remove(class);
+        self.props_mut().set("class", classes.into_attr());
+        self
+    }
+
+    fn toggle_class(mut self, class: &str) -> Self {
+        let mut classes = self.class_list();
+        classes.toggle(class);
+        self.props_mut().set("class", classes.into_attr());
+        self
+    }
+
+    /// set `data-{key}`, an error if `key` is not valid, see [`is_valid_data_key`]
+    fn data(mut self, key: &str, value: impl AttrValue) -> Result<Self, InvalidDataKey> {
+        if !is_valid_data_key(key) {
+            return Err(InvalidDataKey(key.to_string()));
+        }
+        self.props_mut()
+            .set(&format!("data-{}", key), value.into_attr());
+        Ok(self)
+    }
+
+    fn aria(mut self, aria: Aria) -> Self {
+        let (name, value) = aria.pair();
+        self.props_mut().set(name, Some(value));
+        self
+    }
+
+    fn role(mut self, role: Role) -> Self {
+        self.props_mut().set("role", role.into_attr());
+        self
+    }
+}
+
+impl GlobalAttrs for UnitTag {
+    fn props(&self) -> &TagProp {
+        &self.props
+    }
+
+    fn props_mut(&mut self) -> &mut TagProp {
+        &mut self.props
+    }
+}
@@ -1,27 +1,71 @@
 use super::TagFormatter;
+use crate::content::{Category, Content, Flow, NonInteractive, Permitted, Phrasing, Text};
+use crate::escape::{escape_comment, escape_doctype};
+use crate::tags::UnitTag;
 use crate::Children;
+use crate::Comment;
+use crate::Doctype;
+use crate::Either;
 use crate::InnerChildren;
 use crate::Kong;
+use crate::Raw;
 use crate::Tag;
+use std::any::Any;
+use std::borrow::Cow;
 use std::fmt::Write;
+use std::iter::{
+    Chain, Filter, FilterMap, FlatMap, Flatten, Map, Peekable, Rev, Skip, SkipWhile, Take,
+    TakeWhile,
+};
+use std::rc::Rc;
+use std::sync::Arc;
 
 macro_rules! impl_basic {
-    ($t:ty) => {
+    (@impl $t:ty, $($any:tt)*) => {
         impl Tag for $t {
-            fn name(&self) -> &'static str {
-                stringify!($t)
+            fn name(&self) -> &str {
+                "#text"
+            }
+
+            fn format(&self, f: &mut TagFormatter, buf: &mut dyn Write) -> std::fmt::Result {
+                f.begin_line(buf)?;
+                f.write_text(buf, &self.to_string())?;
+                f.end_line(buf)
             }
 
-            fn format(&self, f: &mut TagFormatter, buf: &mut String) -> std::fmt::Result {
-                let pad = f.pad_size();
-                write!(buf, "{:pad$}{}{}", "", self, f.line_sep)
+            fn categories(&self) -> &'static [Category] {
+                Category::TEXT
             }
+
+            $($any)*
         }
+
+        impl Flow for $t {}
+        impl Phrasing for $t {}
+        impl NonInteractive for $t {}
+        impl Text for $t {}
+    };
+    // borrowed text can not be downcast
+    (borrowed $t:ty) => {
+        impl_basic!(@impl $t,);
+    };
+    ($t:ty) => {
+        impl_basic!(@impl $t,
+            fn as_any(&self) -> Option<&dyn Any> {
+                Some(self)
+            }
+
+            fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+                Some(self)
+            }
+        );
     };
 }
 
 impl_basic!(String);
-impl_basic!(&str);
+impl_basic!(borrowed &str);
+impl_basic!(borrowed Cow<'_, str>);
+impl_basic!(char);
 impl_basic!(bool);
 impl_basic!(u8);
 impl_basic!(u16);
@@ -43,25 +87,228 @@ impl std::fmt::Display for Kong {
 }
 
 impl Tag for Kong {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         ""
     }
 
-    fn format(&self, _f: &mut crate::tags::TagFormatter, _buf: &mut String) -> std::fmt::Result {
+    fn format(&self, _f: &mut TagFormatter, _buf: &mut dyn Write) -> std::fmt::Result {
         Ok(())
     }
 }
 
+impl std::fmt::Display for Raw {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Tag for Raw {
+    fn name(&self) -> &str {
+        "#raw"
+    }
+
+    fn format(&self, f: &mut TagFormatter, buf: &mut dyn Write) -> std::fmt::Result {
+        f.begin_line(buf)?;
+        buf.write_str(&self.0)?;
+        f.end_line(buf)
+    }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
+}
+
+impl Tag for Comment {
+    fn name(&self) -> &str {
+        "#comment"
+    }
+
+    fn format(&self, f: &mut TagFormatter, buf: &mut dyn Write) -> std::fmt::Result {
+        f.begin_line(buf)?;
+        buf.write_str("<!--")?;
+        escape_comment(&self.0, buf)?;
+        buf.write_str("-->")?;
+        f.end_line(buf)
+    }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
+}
+
+impl Tag for Doctype {
+    fn name(&self) -> &str {
+        "#doctype"
+    }
+
+    fn format(&self, f: &mut TagFormatter, buf: &mut dyn Write) -> std::fmt::Result {
+        f.begin_line(buf)?;
+        buf.write_str("<!DOCTYPE ")?;
+        escape_doctype(&self.0, buf)?;
+        buf.write_char('>')?;
+        f.end_line(buf)
+    }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
+}
+
+impl From<String> for Raw {
+    fn from(src: String) -> Self {
+        Self(src)
+    }
+}
+
+impl From<&str> for Raw {
+    fn from(src: &str) -> Self {
+        Self(src.to_string())
+    }
+}
+
 impl Tag for () {
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         ""
     }
 
-    fn format(&self, _f: &mut crate::tags::TagFormatter, _buf: &mut String) -> std::fmt::Result {
+    fn format(&self, _f: &mut TagFormatter, _buf: &mut dyn Write) -> std::fmt::Result {
         Ok(())
     }
 }
 
+/// delegate to the tag inside, for wrappers and enums of tags, `$inner` and `$inner_mut`
+/// are `None` if there is no tag
+macro_rules! forward_tag {
+    ([$($bounds:tt)*] $t:ty, $self:ident => $inner:expr, $inner_mut:expr) => {
+        impl<$($bounds)*> Tag for $t {
+            fn name(&$self) -> &str {
+                $inner.map_or("", |tag| tag.name())
+            }
+
+            fn format(&$self, f: &mut TagFormatter, buf: &mut dyn Write) -> std::fmt::Result {
+                $inner.map_or(Ok(()), |tag| tag.format(f, buf))
+            }
+
+            fn as_element(&$self) -> Option<&UnitTag> {
+                $inner.and_then(|tag| tag.as_element())
+            }
+
+            fn as_element_mut(&mut $self) -> Option<&mut UnitTag> {
+                $inner_mut.and_then(|tag| tag.as_element_mut())
+            }
+
+            fn as_any(&$self) -> Option<&dyn Any> {
+                $inner.and_then(|tag| tag.as_any())
+            }
+
+            fn as_any_mut(&mut $self) -> Option<&mut dyn Any> {
+                $inner_mut.and_then(|tag| tag.as_any_mut())
+            }
+
+            fn categories(&$self) -> &'static [Category] {
+                $inner.map_or(Category::UNCHECKED, |tag| tag.categories())
+            }
+
+            fn permits(&$self, child: &dyn Tag) -> bool {
+                $inner.is_none_or(|tag| tag.permits(child))
+            }
+        }
+    };
+}
+
+// renders nothing if `None`
+forward_tag!([T: Tag] Option<T>, self => self.as_ref(), self.as_mut());
+forward_tag!(
+    [T: Tag, E: Tag] Result<T, E>,
+    self => match self {
+        Ok(tag) => Some(tag as &dyn Tag),
+        Err(tag) => Some(tag as &dyn Tag),
+    },
+    match self {
+        Ok(tag) => Some(tag as &mut dyn Tag),
+        Err(tag) => Some(tag as &mut dyn Tag),
+    }
+);
+forward_tag!(
+    [L: Tag, R: Tag] Either<L, R>,
+    self => match self {
+        Either::Left(tag) => Some(tag as &dyn Tag),
+        Either::Right(tag) => Some(tag as &dyn Tag),
+    },
+    match self {
+        Either::Left(tag) => Some(tag as &mut dyn Tag),
+        Either::Right(tag) => Some(tag as &mut dyn Tag),
+    }
+);
+// shared tags can only be modified if there is no other reference
+forward_tag!([T: Tag + ?Sized] Rc<T>, self => Some(self.as_ref()), Rc::get_mut(self));
+forward_tag!([T: Tag + ?Sized] Arc<T>, self => Some(self.as_ref()), Arc::get_mut(self));
+
+impl From<Box<dyn Tag>> for Children {
+    fn from(src: Box<dyn Tag>) -> Self {
+        Self(vec![src])
+    }
+}
+
+impl From<InnerChildren> for Children {
+    fn from(src: InnerChildren) -> Self {
+        Self(src)
+    }
+}
+
+/// iterator adaptors yielding tags are children, also in tuples, no need to collect them first
+macro_rules! iter_impl {
+    ($($iter:ident<$($g:ident),+> $([$($bound:tt)*])?),+ $(,)?) => {
+        $(
+            impl<$($g),+> From<$iter<$($g),+>> for Children
+            where
+                $($($bound)*,)?
+                $iter<$($g),+>: Iterator,
+                <$iter<$($g),+> as Iterator>::Item: Tag + 'static,
+            {
+                fn from(src: $iter<$($g),+>) -> Self {
+                    src.collect()
+                }
+            }
+
+            impl<Model, $($g),+> Content<Model> for $iter<$($g),+>
+            where
+                $($($bound)*,)?
+                $iter<$($g),+>: Iterator,
+                <$iter<$($g),+> as Iterator>::Item: Tag + Permitted<Model> + 'static,
+            {
+            }
+        )+
+    };
+}
+
+iter_impl!(
+    Map<I, F>,
+    Filter<I, P>,
+    FilterMap<I, F>,
+    FlatMap<I, U, F> [U: IntoIterator],
+    Flatten<I> [I: Iterator<Item: IntoIterator>],
+    Chain<A, B>,
+    Take<I>,
+    Skip<I>,
+    TakeWhile<I, P>,
+    SkipWhile<I, P>,
+    Rev<I>,
+    Peekable<I> [I: Iterator],
+);
+
 impl<T> From<Vec<T>> for Children
 where
     T: Tag + 'static,
@@ -111,16 +358,20 @@ macro_rules! tuple_impl {
     ($($t:tt),+ | $($i:tt),+) => {
         impl <$($t ),+ > From<($($t,)+)> for Children
             where
-                $($t: Tag + 'static),+
+                $($t: Into<Children>),+
         {
             fn from(src: ($($t,)+)) -> Self {
-                let mut children: InnerChildren = Vec::new();
-                $(
-                    children.push(Box::new(src.$i));
-                )+
-                Self(children)
+                let mut children = Children::default();
+                $(children.append(src.$i);)+
+                children
             }
         }
+
+        impl<Model, $($t),+> Content<Model> for ($($t,)+)
+            where
+                $($t: Content<Model>),+
+        {
+        }
     };
 }
 
@@ -0,0 +1,619 @@
+//! print rust code building the same html with rtml, to port existing templates
+//!
+//! ```text
+//! html2rtml [--document | --fragment] [FILE]
+//! ```
+//!
+//! reads `FILE`, or stdin if it is missing or `-`, and prints a function returning the tree,
+//! written with the tag functions in `rtml::tags`, `prop!`, `style!` and `on!`
+//!
+//! - children of different tags are passed as a tuple, siblings of the same tag as an array,
+//!   or a `Vec` for long lists, attributes, style and handlers come before the children
+//! - whitespace only shown by browsers in `pre`, `textarea` and inline content is kept
+//! - comments become rust comments, the doctype is left to [`rtml::Document`]
+//! - elements without a tag function, such as custom elements and svg, are built with `x`,
+//!   and children the content model does not permit are passed as unchecked `Children`,
+//!   a warning is printed to stderr for both
+//! - attributes `prop!`, `style!` or `on!` do not accept are set with `.attr(name, value)`,
+//!   names which can not be written as attribute names are dropped with a warning
+//!
+//! a page with a doctype or `<html>` is parsed as a whole document, other input as a fragment
+
+use std::{collections::BTreeSet, io::Read, path::Path, process::ExitCode};
+
+use rtml::{
+    attrs::is_valid_attr_name,
+    format::is_inline_level,
+    parse::{parse, parse_document, split_style},
+    style::is_valid_property,
+    tags::{is_builtin, UnitTag},
+    Comment, Doctype, Tag,
+};
+
+/// width generated code is wrapped at
+const MAX_WIDTH: usize = 100;
+/// most children written in one tuple or array, longer runs are split or become a `Vec`
+const MAX_ITEMS: usize = 32;
+
+/// a piece of generated code
+enum Code {
+    Atom(String),
+    /// `head(arg)tail`
+    Call {
+        head: String,
+        arg: List,
+        tail: String,
+    },
+    List(List),
+}
+
+/// items between `open` and `close`, a single bare item if both are empty
+struct List {
+    open: &'static str,
+    close: &'static str,
+    sep: &'static str,
+    /// whether the last item is followed by `sep` when written on several lines
+    trailing: bool,
+    /// whether there is a space inside `open` and `close` when written on one line
+    spaced: bool,
+    items: Vec<Item>,
+    /// comments after the last item
+    end: Vec<String>,
+}
+
+/// generated code of a node and the comments before it
+struct Item {
+    comments: Vec<String>,
+    code: Code,
+    /// type of the tag, siblings of the same type can be an array
+    ty: String,
+}
+
+impl Item {
+    fn new(code: Code) -> Self {
+        Self {
+            comments: vec![],
+            code,
+            ty: String::new(),
+        }
+    }
+}
+
+impl List {
+    fn new(open: &'static str, close: &'static str, sep: &'static str, items: Vec<Item>) -> Self {
+        Self {
+            open,
+            close,
+            sep,
+            trailing: sep == ",",
+            spaced: false,
+            items,
+            end: vec![],
+        }
+    }
+
+    fn bare(item: Item, end: Vec<String>) -> Self {
+        Self {
+            end,
+            ..Self::new("", "", "", vec![item])
+        }
+    }
+
+    fn tuple(items: Vec<Item>, end: Vec<String>) -> Self {
+        Self {
+            end,
+            ..Self::new("(", ")", ",", items)
+        }
+    }
+
+    /// `prop! { .. }`, `style! { .. }` or `on! { .. }`
+    fn macro_call(open: &'static str, sep: &'static str, items: Vec<String>) -> Self {
+        let items = items
+            .into_iter()
+            .map(|i| Item::new(Code::Atom(i)))
+            .collect();
+        Self {
+            spaced: true,
+            ..Self::new(open, "}", sep, items)
+        }
+    }
+
+    fn flat(&self) -> Option<String> {
+        if !self.end.is_empty() || self.items.iter().any(|i| !i.comments.is_empty()) {
+            return None;
+        }
+        let items = self
+            .items
+            .iter()
+            .map(|i| i.code.flat())
+            .collect::<Option<Vec<_>>>()?;
+        let sep = format!("{} ", self.sep);
+        let joined = items.join(&sep);
+        if self.spaced {
+            Some(format!("{} {} {}", self.open, joined, self.close))
+        } else {
+            Some(format!("{}{}{}", self.open, joined, self.close))
+        }
+    }
+
+    fn write(&self, indent: usize, col: usize, out: &mut String) {
+        if let Some(flat) = self.flat().filter(|f| col + f.len() <= MAX_WIDTH) {
+            return out.push_str(&flat);
+        }
+        let bare = self.open.is_empty();
+        if bare && self.end.is_empty() && self.items[0].comments.is_empty() {
+            return self.items[0].code.write(indent, col, out);
+        }
+        let inner = indent + 4;
+        out.push_str(self.open);
+        out.push('\n');
+        for (idx, item) in self.items.iter().enumerate() {
+            write_comments(&item.comments, inner, out);
+            out.push_str(&" ".repeat(inner));
+            item.code.write(inner, inner, out);
+            if self.trailing || idx + 1 < self.items.len() {
+                out.push_str(self.sep);
+            }
+            out.push('\n');
+        }
+        write_comments(&self.end, inner, out);
+        out.push_str(&" ".repeat(indent));
+        out.push_str(self.close);
+    }
+}
+
+impl Code {
+    fn flat(&self) -> Option<String> {
+        match self {
+            Code::Atom(atom) => Some(atom.clone()),
+            Code::Call { head, arg, tail } => Some(format!("{}({}){}", head, arg.flat()?, tail)),
+            Code::List(list) => list.flat(),
+        }
+    }
+
+    fn write(&self, indent: usize, col: usize, out: &mut String) {
+        match self {
+            Code::Atom(atom) => out.push_str(atom),
+            Code::Call { head, arg, tail } => {
+                if let Some(flat) = self.flat().filter(|f| col + f.len() <= MAX_WIDTH) {
+                    return out.push_str(&flat);
+                }
+                out.push_str(head);
+                out.push('(');
+                arg.write(indent, col + head.len() + 1, out);
+                out.push(')');
+                out.push_str(tail);
+            }
+            Code::List(list) => list.write(indent, col, out),
+        }
+    }
+}
+
+fn write_comments(comments: &[String], indent: usize, out: &mut String) {
+    for line in comments {
+        out.push_str(&" ".repeat(indent));
+        out.push_str("//");
+        if !line.is_empty() {
+            out.push(' ');
+            out.push_str(line);
+        }
+        out.push('\n');
+    }
+}
+
+/// `name` is accepted by `style!`, a known, custom or vendor prefixed property
+/// whose parts are identifiers
+fn is_style_name(name: &str) -> bool {
+    let parts = name.strip_prefix("--").or_else(|| name.strip_prefix('-'));
+    is_valid_property(name) && parts.unwrap_or(name).split('-').all(is_ident)
+}
+
+/// whether `part` can be written as a rust identifier in a macro
+fn is_ident(part: &str) -> bool {
+    let mut chars = part.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && part != "_"
+}
+
+/// name as written in `prop!`, `None` if `prop!` does not accept it
+fn prop_name(name: &str) -> Option<String> {
+    let lower = name.to_ascii_lowercase();
+    if lower.starts_with("on") || lower == "style" || !is_valid_attr_name(name) {
+        return None;
+    }
+    if name.split('-').all(is_ident) {
+        Some(name.to_string())
+    } else {
+        Some(format!("{:?}", name))
+    }
+}
+
+/// name of the tag struct of built in element `tag`
+fn struct_name(tag: &str) -> String {
+    if tag == "option" {
+        return "Option_".to_string();
+    }
+    let mut chars = tag.chars();
+    chars
+        .next()
+        .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+/// comments and whitespace text between elements, they are not children of generated code
+fn is_inter_element(node: &(dyn Tag + 'static)) -> bool {
+    node.is::<Comment>()
+        || node
+            .downcast_ref::<String>()
+            .is_some_and(|text| text.trim_ascii().is_empty())
+}
+
+#[derive(Default)]
+struct Generator {
+    /// items imported from rtml besides `tags::*`
+    uses: BTreeSet<&'static str>,
+    /// elements without tag function already warned about
+    warned: BTreeSet<String>,
+}
+
+impl Generator {
+    /// code of `nodes`, children of an element if `parent` is set, and comments after the last one
+    fn nodes(
+        &mut self,
+        parent: Option<&UnitTag>,
+        nodes: &[Box<dyn Tag>],
+        preserve: bool,
+    ) -> (Vec<Item>, Vec<String>) {
+        let parent_inline = parent.is_some_and(|p| is_inline_level(&p.tag));
+        // whether the nearest sibling before or after `idx` is laid out inline, edges of an inline parent are
+        let inline_sibling = |idx: usize, before: bool| {
+            let mut siblings: Box<dyn Iterator<Item = &Box<dyn Tag>>> = if before {
+                Box::new(nodes[..idx].iter().rev())
+            } else {
+                Box::new(nodes[idx + 1..].iter())
+            };
+            siblings
+                .find(|n| !n.is::<Comment>())
+                .map_or(parent_inline, |n| is_inline_level(n.name()))
+        };
+        let mut items = vec![];
+        let mut comments = vec![];
+        for (idx, node) in nodes.iter().enumerate() {
+            let node = node.as_ref();
+            if let Some(comment) = node.downcast_ref::<Comment>() {
+                comments.extend(comment.0.trim().lines().map(|l| l.trim().to_string()));
+                continue;
+            }
+            if node.is::<Doctype>() {
+                eprintln!(
+                    "note: doctype is left out, render the page with rtml::Document to write it"
+                );
+                continue;
+            }
+            let mut item = if let Some(text) = node.downcast_ref::<String>() {
+                let text = if preserve {
+                    text.clone()
+                } else {
+                    collapse(text, inline_sibling(idx, true), inline_sibling(idx, false))
+                };
+                if text.is_empty() {
+                    continue;
+                }
+                Item {
+                    ty: "&str".to_string(),
+                    ..Item::new(Code::Atom(format!("{:?}", text)))
+                }
+            } else if let Some(el) = node.as_element() {
+                self.element(el, preserve)
+            } else {
+                continue;
+            };
+            item.comments = std::mem::take(&mut comments);
+            items.push(item);
+        }
+        (items, comments)
+    }
+
+    fn element(&mut self, el: &UnitTag, preserve: bool) -> Item {
+        let preserve = preserve || matches!(&*el.tag, "pre" | "textarea" | "script" | "style");
+        let builtin = is_builtin(&el.tag);
+        if !builtin && self.warned.insert(el.tag.to_string()) {
+            eprintln!(
+                "warning: <{}> has no tag function in rtml::tags, it is built with x((\"{}\", ..))",
+                el.tag, el.tag
+            );
+        }
+        let mut parts = vec![];
+        let mut attrs = vec![];
+        let mut props = vec![];
+        let mut style = None;
+        let mut handlers = vec![];
+        for (name, value) in el.props.0.iter() {
+            if !is_valid_attr_name(name) {
+                eprintln!(
+                    "warning: <{}> has attribute {:?} which is not a valid attribute name, it is dropped",
+                    el.tag, name
+                );
+                continue;
+            }
+            let event = name.strip_prefix("on").filter(|event| is_ident(event));
+            match prop_name(name) {
+                Some(name) if value.is_empty() => props.push(name),
+                Some(name) => props.push(format!("{} = {:?}", name, value)),
+                None if name == "style" => match split_style(value) {
+                    Some(decls) if decls.keys().all(|name| is_style_name(name)) => {
+                        style = Some(decls)
+                    }
+                    _ => attrs.push((name.clone(), value.clone())),
+                },
+                None => match event {
+                    Some(event) => handlers.push(format!("{} = {:?}", event, value)),
+                    None => attrs.push((name.clone(), value.clone())),
+                },
+            }
+        }
+        if !props.is_empty() {
+            self.uses.insert("prop");
+            parts.push(List::macro_call("prop! {", ",", props));
+        }
+        if let Some(decls) = style {
+            self.uses.insert("style");
+            let decls = decls
+                .iter()
+                .map(|(name, value)| format!("{}: {:?}", name, value));
+            parts.push(List {
+                trailing: false,
+                ..List::macro_call("style! {", ";", decls.collect())
+            });
+        }
+        if !handlers.is_empty() {
+            self.uses.insert("on");
+            parts.push(List::macro_call("on! {", ",", handlers));
+        }
+
+        let (items, trailing) = self.nodes(Some(el), &el.children, preserve);
+        let permitted = !builtin
+            || el
+                .children
+                .iter()
+                .all(|c| is_inter_element(c.as_ref()) || el.permits(c.as_ref()));
+        if !permitted {
+            eprintln!(
+                "warning: <{}> has children its content model does not permit, they are passed as unchecked Children",
+                el.tag
+            );
+            self.uses.insert("Children");
+        }
+        let mut args: Vec<Item> = parts
+            .into_iter()
+            .map(|part| Item::new(Code::List(part)))
+            .collect();
+        let mut end = vec![];
+        match children(items, trailing) {
+            Some((comments, mut code, after)) => {
+                if !permitted {
+                    code = Code::Call {
+                        head: "Children::from".to_string(),
+                        arg: List::bare(Item::new(code), vec![]),
+                        tail: String::new(),
+                    };
+                }
+                args.push(Item {
+                    comments,
+                    ..Item::new(code)
+                });
+                end = after;
+            }
+            // built in tags can take only prop, style and handlers, without children
+            None if !builtin || args.is_empty() => {
+                args.push(Item::new(Code::Atom("()".to_string())))
+            }
+            None => {}
+        }
+        let head = match &*el.tag {
+            _ if !builtin => {
+                args.insert(0, Item::new(Code::Atom(format!("{:?}", el.tag))));
+                "x".to_string()
+            }
+            // a local `fn main` shadows the tag function
+            "main" => "rtml::tags::main".to_string(),
+            tag => tag.to_string(),
+        };
+        let arg = match args.len() {
+            1 => List::bare(args.pop().unwrap(), end),
+            _ => List::tuple(args, end),
+        };
+        let tail = attrs
+            .iter()
+            .map(|(name, value)| format!(".attr({:?}, {:?})", name, value))
+            .collect();
+        Item {
+            ty: if builtin {
+                struct_name(&el.tag)
+            } else {
+                "UnitTag".to_string()
+            },
+            ..Item::new(Code::Call { head, arg, tail })
+        }
+    }
+}
+
+/// children as one code: a single item, an array or `Vec` of items of the same type, or a tuple,
+/// with the comments before it and after it
+fn children(mut items: Vec<Item>, end: Vec<String>) -> Option<(Vec<String>, Code, Vec<String>)> {
+    match items.len() {
+        0 if end.is_empty() => None,
+        0 => Some((end, Code::Atom("()".to_string()), vec![])),
+        1 => {
+            let item = items.pop().unwrap();
+            Some((item.comments, item.code, end))
+        }
+        len if items.iter().all(|i| i.ty == items[0].ty) => {
+            let list = if len > MAX_ITEMS {
+                List::new("vec![", "]", ",", items)
+            } else {
+                List::new("[", "]", ",", items)
+            };
+            Some((vec![], Code::List(List { end, ..list }), vec![]))
+        }
+        len if len > MAX_ITEMS => {
+            let mut chunks = vec![];
+            while !items.is_empty() {
+                let rest = items.split_off(items.len().min(MAX_ITEMS));
+                chunks.push(Item::new(Code::List(List::tuple(items, vec![]))));
+                items = rest;
+            }
+            Some((vec![], Code::List(List::tuple(chunks, end)), vec![]))
+        }
+        _ => Some((vec![], Code::List(List::tuple(items, end)), vec![])),
+    }
+}
+
+/// collapse whitespace runs of text, dropping it at edges where browsers do not show it
+fn collapse(text: &str, inline_before: bool, inline_after: bool) -> String {
+    let mut out = String::with_capacity(text.len());
+    for (idx, word) in text.split_ascii_whitespace().enumerate() {
+        if idx > 0 {
+            out.push(' ');
+        }
+        out.push_str(word);
+    }
+    if text.trim_ascii().is_empty() {
+        if inline_before && inline_after && !text.is_empty() {
+            return " ".to_string();
+        }
+        return String::new();
+    }
+    if inline_before && text.starts_with(|c: char| c.is_ascii_whitespace()) {
+        out.insert(0, ' ');
+    }
+    if inline_after && text.ends_with(|c: char| c.is_ascii_whitespace()) {
+        out.push(' ');
+    }
+    out
+}
+
+/// name of the generated function, from the file name
+fn fn_name(path: Option<&str>) -> String {
+    const RESERVED: &[&str] = &[
+        "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else",
+        "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "main", "match",
+        "mod", "move", "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait",
+        "true", "type", "unsafe", "use", "where", "while", "x",
+    ];
+    let stem = path
+        .and_then(|p| Path::new(p).file_stem())
+        .map(|s| s.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    let name: String = stem
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    match name.trim_matches('_') {
+        "" => "page".to_string(),
+        name if name.starts_with(|c: char| c.is_ascii_digit()) => format!("page_{}", name),
+        // a function named like a tag function shadows it
+        name if RESERVED.contains(&name) || is_builtin(name) => format!("{}_page", name),
+        name => name.to_string(),
+    }
+}
+
+/// generated rust source for `html`
+fn generate(html: &str, document: bool, name: &str) -> String {
+    let nodes = if document {
+        parse_document(html)
+    } else {
+        parse(html)
+    };
+    let mut gen = Generator::default();
+    let (mut items, end) = gen.nodes(None, &nodes.0, false);
+    let (ret, body) = match items.len() {
+        1 if end.is_empty() => {
+            let item = items.pop().unwrap();
+            let ret = match item.ty.as_str() {
+                "&str" => "&'static str".to_string(),
+                ty => ty.to_string(),
+            };
+            (ret, List::bare(item, vec![]))
+        }
+        _ => {
+            gen.uses.insert("Children");
+            let code = match children(items, end) {
+                Some((comments, code, trailing)) => Code::Call {
+                    head: "Children::from".to_string(),
+                    arg: List::bare(
+                        Item {
+                            comments,
+                            ..Item::new(code)
+                        },
+                        trailing,
+                    ),
+                    tail: String::new(),
+                },
+                None => Code::Atom("Children::default()".to_string()),
+            };
+            ("Children".to_string(), List::bare(Item::new(code), vec![]))
+        }
+    };
+    let mut uses: Vec<&str> = gen.uses.into_iter().collect();
+    uses.push("tags::*");
+    let mut out = match uses.as_slice() {
+        [only] => format!("use rtml::{};\n\n", only),
+        _ => format!("use rtml::{{{}}};\n\n", uses.join(", ")),
+    };
+    out.push_str(&format!("pub fn {}() -> {} {{\n    ", name, ret));
+    body.write(4, 4, &mut out);
+    out.push_str("\n}\n");
+    out
+}
+
+fn main() -> ExitCode {
+    let mut document = None;
+    let mut path = None;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--document" => document = Some(true),
+            "--fragment" => document = Some(false),
+            "-h" | "--help" => {
+                println!("usage: html2rtml [--document | --fragment] [FILE]");
+                println!(
+                    "print rtml code building the html in FILE, or stdin if FILE is missing or -"
+                );
+                return ExitCode::SUCCESS;
+            }
+            "-" => path = None,
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg),
+            _ => {
+                eprintln!("error: unexpected argument {}, see --help", arg);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    let html = match &path {
+        Some(path) => std::fs::read_to_string(path),
+        None => {
+            let mut html = String::new();
+            std::io::stdin().read_to_string(&mut html).map(|_| html)
+        }
+    };
+    let html = match html {
+        Ok(html) => html,
+        Err(e) => {
+            eprintln!(
+                "error: failed to read {}: {}",
+                path.as_deref().unwrap_or("stdin"),
+                e
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+    let document = document.unwrap_or_else(|| {
+        let lower = html.trim_start().to_ascii_lowercase();
+        lower.starts_with("<!doctype") || lower.contains("<html")
+    });
+    print!("{}", generate(&html, document, &fn_name(path.as_deref())));
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,310 @@
+//! html content categories are marker traits, each tag function only accepts
+//! children permitted by its content model, so invalid trees fail to compile
+//!
+//! ```compile_fail
+//! use rtml::tags::*;
+//! let list = ul(div("not a list item"));
+//! ```
+//!
+//! ```compile_fail
+//! use rtml::tags::*;
+//! let para = p(div("block in paragraph"));
+//! ```
+//!
+//! ```compile_fail
+//! use rtml::tags::*;
+//! let link = a(a("nested link"));
+//! ```
+//!
+//! ```compile_fail
+//! use rtml::tags::*;
+//! let list = ul(Some(div("not a list item")));
+//! ```
+//!
+//! ```compile_fail
+//! use rtml::tags::*;
+//! let list = Ul::from((div("a"), div("b")));
+//! ```
+//!
+//! text, [`Raw`], [`Comment`], `()`, [`UnitTag`] built by [`x`](crate::tags::x), [`Children`],
+//! `Box<dyn Tag>` and `#[component]`s are not checked, they are permitted wherever script supporting,
+//! flow, phrasing or metadata content is. `Option`, `Result`, [`Either`], `Rc`, `Arc`
+//! and iterators are permitted if the tags inside are
+
+use std::{marker::PhantomData, rc::Rc, sync::Arc};
+
+use crate::{
+    document::InHead,
+    tags::{
+        Body, Caption, Col, Colgroup, Dd, Div, Dt, Figcaption, Head, Hr, Img, Legend, Li, Optgroup,
+        Option_, Rp, Rt, Source, Summary, Tbody, Td, Template, Tfoot, Th, Thead, Tr, Track,
+        UnitTag,
+    },
+    Children, Comment, Either, InnerChildren, Kong, Raw, Tag,
+};
+
+/// metadata content, `base`, `link`, `meta`, `script`, `style`, `title`...
+pub trait Metadata {}
+/// flow content, most elements used in body
+pub trait Flow {}
+/// sectioning content, `article`, `aside`, `nav`, `section`
+pub trait Sectioning {}
+/// heading content, `h1` to `h6`
+pub trait Heading {}
+/// phrasing content, text and the elements marking it up
+pub trait Phrasing {}
+/// embedded content, `img`, `video`, `iframe`...
+pub trait Embedded {}
+/// interactive content, `a`, `button`, `input`...
+pub trait Interactive {}
+/// script supporting elements, `script` and `template`
+pub trait ScriptSupporting {}
+/// flow or phrasing content which is not interactive, allowed in `a` and `button`
+pub trait NonInteractive {}
+/// text nodes
+pub trait Text {}
+
+/// content categories at runtime, one for each marker trait above
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    Metadata,
+    Flow,
+    Sectioning,
+    Heading,
+    Phrasing,
+    Embedded,
+    Interactive,
+    ScriptSupporting,
+    NonInteractive,
+    Text,
+}
+
+impl Category {
+    /// categories of text nodes
+    pub const TEXT: &'static [Category] = &[
+        Category::Flow,
+        Category::Phrasing,
+        Category::NonInteractive,
+        Category::Text,
+    ];
+    /// categories of content which is not checked, see [`unchecked_content!`](crate::unchecked_content)
+    pub const UNCHECKED: &'static [Category] = &[
+        Category::Metadata,
+        Category::Flow,
+        Category::Phrasing,
+        Category::ScriptSupporting,
+        Category::NonInteractive,
+        Category::Text,
+    ];
+}
+
+/// `Self` may be a child of elements whose content model is `M`
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not permitted in `{M}`",
+    label = "not permitted here by the html content model"
+)]
+pub trait Permitted<M> {}
+
+/// children permitted by content model `M`, a single tag, a tuple, `Vec` or array of them
+pub trait Content<M>: Into<Children> {}
+
+macro_rules! content_model {
+    ($($(#[$doc:meta])* $model:ident),+ $(,)?) => {
+        $(
+            $(#[$doc])*
+            pub struct $model;
+        )+
+    };
+}
+
+content_model!(
+    /// flow content
+    FlowContent,
+    /// phrasing content
+    PhrasingContent,
+    /// flow content without interactive content, e.g. `a`
+    FlowNoInteractive,
+    /// phrasing content without interactive content, e.g. `button`
+    PhrasingNoInteractive,
+    /// metadata content, `head`
+    MetadataContent,
+    /// text only, e.g. `title`, `textarea`, `script`
+    TextContent,
+    /// nothing, e.g. `iframe`
+    NoContent,
+    /// anything, `template`
+    AnyContent,
+    /// `head` and `body`, `html`
+    HtmlContent,
+    /// `li`, `ul`, `ol` and `menu`
+    ListContent,
+    /// `dt`, `dd` and `div`, `dl`
+    DlContent,
+    /// `caption`, `colgroup`, `thead`, `tbody`, `tfoot` and `tr`, `table`
+    TableContent,
+    /// `tr`, `thead`, `tbody` and `tfoot`
+    TableSectionContent,
+    /// `td` and `th`, `tr`
+    RowContent,
+    /// `col` and `template`, `colgroup`
+    ColgroupContent,
+    /// `option`, `optgroup` and `hr`, `select`
+    SelectContent,
+    /// `option`, `optgroup`
+    OptgroupContent,
+    /// phrasing content and `option`, `datalist`
+    DatalistContent,
+    /// `source` and `img`, `picture`
+    PictureContent,
+    /// `source`, `track` and flow content, `audio` and `video`
+    MediaContent,
+    /// phrasing content, `rp` and `rt`, `ruby`
+    RubyContent,
+    /// `legend` and flow content, `fieldset`
+    FieldsetContent,
+    /// `summary` and flow content, `details`
+    DetailsContent,
+    /// `figcaption` and flow content, `figure`
+    FigureContent,
+);
+
+/// checks children against a content model at runtime, for trees only known when rendering,
+/// see [`crate::validate`]
+pub trait ContentModel {
+    fn permits(child: &dyn Tag) -> bool;
+}
+
+/// wrappers and branches are permitted if the tags inside are
+macro_rules! wrappers {
+    ($model:ident) => {
+        impl<T: Permitted<$model>> Permitted<$model> for Option<T> {}
+        impl<T: Permitted<$model>, E: Permitted<$model>> Permitted<$model> for Result<T, E> {}
+        impl<L: Permitted<$model>, R: Permitted<$model>> Permitted<$model> for Either<L, R> {}
+        impl<T: Permitted<$model> + ?Sized> Permitted<$model> for Rc<T> {}
+        impl<T: Permitted<$model> + ?Sized> Permitted<$model> for Arc<T> {}
+    };
+}
+
+/// define a content model permitting the listed tags and anything in all of the listed categories,
+/// both as [`Permitted`] impls and as [`ContentModel`]
+macro_rules! model {
+    ($model:ident: [$($tag:ident),*]) => {
+        $(impl Permitted<$model> for $tag {})*
+        wrappers!($model);
+
+        impl ContentModel for $model {
+            fn permits(child: &dyn Tag) -> bool {
+                [$($tag::NAME),*].contains(&child.name())
+            }
+        }
+    };
+    ($model:ident: [$($tag:ident),*], [$($cat:ident),+]) => {
+        impl<T: $($cat+)*> Permitted<$model> for T {}
+        $(impl Permitted<$model> for $tag {})*
+        wrappers!($model);
+
+        impl ContentModel for $model {
+            fn permits(child: &dyn Tag) -> bool {
+                [$($tag::NAME),*].contains(&child.name())
+                    || [$(Category::$cat),*].iter().all(|c| child.categories().contains(c))
+            }
+        }
+    };
+}
+
+model!(FlowContent: [], [Flow]);
+model!(PhrasingContent: [], [Phrasing]);
+model!(FlowNoInteractive: [], [Flow, NonInteractive]);
+model!(PhrasingNoInteractive: [], [Phrasing, NonInteractive]);
+model!(MetadataContent: [], [Metadata]);
+model!(TextContent: [], [Text]);
+model!(HtmlContent: [Head, Body]);
+model!(ListContent: [Li], [ScriptSupporting]);
+model!(DlContent: [Dt, Dd, Div], [ScriptSupporting]);
+model!(TableContent: [Caption, Colgroup, Thead, Tbody, Tfoot, Tr], [ScriptSupporting]);
+model!(TableSectionContent: [Tr], [ScriptSupporting]);
+model!(RowContent: [Td, Th], [ScriptSupporting]);
+model!(ColgroupContent: [Col, Template]);
+model!(SelectContent: [Option_, Optgroup, Hr], [ScriptSupporting]);
+model!(OptgroupContent: [Option_], [ScriptSupporting]);
+model!(DatalistContent: [Option_], [Phrasing]);
+model!(PictureContent: [Source, Img], [ScriptSupporting]);
+model!(MediaContent: [Source, Track], [Flow]);
+model!(RubyContent: [Rp, Rt], [Phrasing]);
+model!(FieldsetContent: [Legend], [Flow]);
+model!(DetailsContent: [Summary], [Flow]);
+model!(FigureContent: [Figcaption], [Flow]);
+
+impl Permitted<NoContent> for () {}
+
+impl ContentModel for NoContent {
+    fn permits(_child: &dyn Tag) -> bool {
+        false
+    }
+}
+
+impl<T: Tag> Permitted<AnyContent> for T {}
+
+impl ContentModel for AnyContent {
+    fn permits(_child: &dyn Tag) -> bool {
+        true
+    }
+}
+
+/// implement the categories of content which is not checked
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unchecked_content {
+    ($($t:ty),+) => {
+        $(
+            impl $crate::content::Metadata for $t {}
+            impl $crate::content::Flow for $t {}
+            impl $crate::content::Phrasing for $t {}
+            impl $crate::content::ScriptSupporting for $t {}
+            impl $crate::content::NonInteractive for $t {}
+            impl $crate::content::Text for $t {}
+        )+
+    };
+}
+
+unchecked_content!(Raw, Comment, Kong, (), UnitTag, InHead);
+
+impl<M, T: Tag + Permitted<M> + 'static> Content<M> for T {}
+impl<M, T: Tag + Permitted<M> + 'static> Content<M> for Vec<T> {}
+impl<M, T: Tag + Permitted<M> + 'static, const N: usize> Content<M> for [T; N] {}
+/// dynamic children are not checked
+impl<M> Content<M> for Children {}
+impl<M> Content<M> for Box<dyn Tag> {}
+impl<M> Content<M> for InnerChildren {}
+impl<M, const N: usize> Content<M> for [Box<dyn Tag>; N] {}
+
+/// children checked against content model `M` one by one, used by `html!`
+pub struct ChildrenOf<M>(pub Children, PhantomData<M>);
+
+impl<M> ChildrenOf<M> {
+    pub fn new() -> Self {
+        Self(Children(vec![]), PhantomData)
+    }
+
+    pub fn push<T: Tag + Permitted<M> + 'static>(&mut self, item: T) {
+        self.0.push(item)
+    }
+
+    pub fn append<C: Content<M>>(&mut self, children: C) {
+        self.0.append(children)
+    }
+}
+
+impl<M> Default for ChildrenOf<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M> From<ChildrenOf<M>> for Children {
+    fn from(src: ChildrenOf<M>) -> Self {
+        src.0
+    }
+}
+
+impl<M> Content<M> for ChildrenOf<M> {}
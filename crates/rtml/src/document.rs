@@ -0,0 +1,300 @@
+use std::fmt::{Display, Write};
+
+use crate::{
+    format::TagFormatter,
+    style::scoped_css,
+    tags::{
+        body, meta, script, style, title, x, Body, Link, Meta, Script, Style, TagProp, UnitTag,
+    },
+    validate::{Diagnostic, Validator},
+    Children, Raw, Tag,
+};
+
+/// typed content of `<head>`
+pub struct DocumentHead {
+    /// `<meta charset>`, `utf-8` by default
+    pub charset: Option<String>,
+    /// `<meta name="viewport">`
+    pub viewport: Option<String>,
+    pub title: Option<String>,
+    pub meta: Vec<Meta>,
+    pub links: Vec<Link>,
+    pub styles: Vec<Style>,
+    pub scripts: Vec<Script>,
+}
+
+impl Default for DocumentHead {
+    fn default() -> Self {
+        Self {
+            charset: Some("utf-8".to_string()),
+            viewport: None,
+            title: None,
+            meta: vec![],
+            links: vec![],
+            styles: vec![],
+            scripts: vec![],
+        }
+    }
+}
+
+/// a complete standards mode html page, rendered with `<!DOCTYPE html>`
+///
+/// ```
+/// use rtml::{prop, tags::*, Document};
+///
+/// let page = Document::new()
+///     .lang("en")
+///     .title("hello")
+///     .link(link(prop! { rel = "stylesheet", href = "main.css" }))
+///     .body(body(h1("hello world!")));
+/// let html = page.to_string();
+/// assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"en\">"));
+/// assert!(html.contains(r#"<meta charset="utf-8">"#));
+/// ```
+pub struct Document {
+    pub lang: Option<String>,
+    pub head: DocumentHead,
+    pub body: Body,
+}
+
+impl Default for Document {
+    fn default() -> Self {
+        Self {
+            lang: None,
+            head: DocumentHead::default(),
+            body: body(()),
+        }
+    }
+}
+
+impl Document {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// set `lang` of `<html>`
+    pub fn lang<S: Into<String>>(mut self, lang: S) -> Self {
+        self.lang = Some(lang.into());
+        self
+    }
+
+    /// set document charset, `None` to omit `<meta charset>`
+    pub fn charset<S: Into<String>>(mut self, charset: Option<S>) -> Self {
+        self.head.charset = charset.map(Into::into);
+        self
+    }
+
+    /// set `<meta name="viewport">`
+    pub fn viewport<S: Into<String>>(mut self, content: S) -> Self {
+        self.head.viewport = Some(content.into());
+        self
+    }
+
+    pub fn title<S: Into<String>>(mut self, title: S) -> Self {
+        self.head.title = Some(title.into());
+        self
+    }
+
+    /// add a `<meta>` to head
+    pub fn meta(mut self, meta: Meta) -> Self {
+        self.head.meta.push(meta);
+        self
+    }
+
+    /// add a `<link>` to head
+    pub fn link(mut self, link: Link) -> Self {
+        self.head.links.push(link);
+        self
+    }
+
+    /// add a `<style>` to head
+    pub fn style(mut self, style: Style) -> Self {
+        self.head.styles.push(style);
+        self
+    }
+
+    /// add a `<script>` to head
+    pub fn script(mut self, script: Script) -> Self {
+        self.head.scripts.push(script);
+        self
+    }
+
+    /// set page body
+    pub fn body(mut self, body: Body) -> Self {
+        self.body = body;
+        self
+    }
+
+    /// check the whole page, head entries and body share ids and elements which may only appear once,
+    /// see [`crate::validate`]
+    ///
+    /// ```
+    /// use rtml::{tags::{self, *}, Document};
+    ///
+    /// let page = Document::new()
+    ///     .title("hello")
+    ///     .body(body((tags::main(()), x(("title", "again")), tags::main(()))));
+    /// let problems: Vec<_> = page.validate().iter().map(ToString::to_string).collect();
+    /// assert_eq!(
+    ///     problems,
+    ///     [
+    ///         "html: `html` is missing required attribute `lang`",
+    ///         "html > body: `title` is not permitted in `body`",
+    ///         "html > body > title: `title` should only appear once",
+    ///         "html > body > main: `main` should only appear once",
+    ///     ]
+    /// );
+    /// ```
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut validator = Validator::new();
+        if self.lang.is_none() {
+            validator.missing("html", "html", "lang");
+        }
+        if self.head.title.is_some() {
+            validator.once("html > head > title", "title");
+        }
+        let own = self
+            .head
+            .meta
+            .iter()
+            .map(|t| t as &dyn Tag)
+            .chain(self.head.links.iter().map(|t| t as &dyn Tag))
+            .chain(self.head.styles.iter().map(|t| t as &dyn Tag))
+            .chain(self.head.scripts.iter().map(|t| t as &dyn Tag));
+        for tag in own {
+            validator.visit(tag, "html > head");
+        }
+        validator.visit(&self.body, "html");
+        validator.diagnostics
+    }
+}
+
+impl Tag for Document {
+    fn name(&self) -> &str {
+        "html"
+    }
+
+    /// body is rendered first, into a buffer, so head entries contributed by [`in_head`],
+    /// js functions used by handlers and scoped stylesheets from anywhere in the body
+    /// are known when head is written
+    fn format(&self, f: &mut TagFormatter, buf: &mut dyn Write) -> std::fmt::Result {
+        let entries = std::mem::take(&mut f.head);
+        let scripts = std::mem::take(&mut f.scripts);
+        let styles = std::mem::take(&mut f.styles);
+        f.indent += 1;
+        let mut body = String::new();
+        self.body.format(f, &mut body)?;
+        f.indent -= 1;
+        let collected = std::mem::replace(&mut f.head, entries);
+        let functions = std::mem::replace(&mut f.scripts, scripts);
+        let sheets = std::mem::replace(&mut f.styles, styles);
+
+        let mut children = Children(vec![]);
+        if let Some(charset) = &self.head.charset {
+            children.push(meta(TagProp([("charset", charset)].into_iter().collect())));
+        }
+        if let Some(viewport) = &self.head.viewport {
+            let props = [("name", "viewport"), ("content", viewport)];
+            children.push(meta(TagProp(props.into_iter().collect())));
+        }
+        if let Some(text) = &self.head.title {
+            children.push(title(text.clone()));
+        }
+        let own = self
+            .head
+            .meta
+            .iter()
+            .map(|t| t as &dyn Tag)
+            .chain(self.head.links.iter().map(|t| t as &dyn Tag))
+            .chain(self.head.styles.iter().map(|t| t as &dyn Tag))
+            .chain(self.head.scripts.iter().map(|t| t as &dyn Tag));
+        for tag in own {
+            let mut entry = String::new();
+            TagFormatter::compact().render(tag, &mut entry)?;
+            children.push(Raw(entry));
+        }
+        for entry in collected {
+            children.push(Raw(entry));
+        }
+        if !sheets.is_empty() {
+            children.push(style(scoped_css(&sheets)?));
+        }
+        if !functions.is_empty() {
+            children.push(script(functions.join(f.line_sep)));
+        }
+
+        f.begin_line(buf)?;
+        buf.write_str("<!DOCTYPE html>")?;
+        f.end_line(buf)?;
+        f.begin_line(buf)?;
+        buf.write_str("<html")?;
+        if let Some(lang) = &self.lang {
+            buf.write_char(' ')?;
+            f.write_attr(buf, "lang", lang)?;
+        }
+        buf.write_char('>')?;
+        f.end_line(buf)?;
+        f.indent += 1;
+        x(("head", children)).format(f, buf)?;
+        f.indent -= 1;
+        buf.write_str(&body)?;
+        f.begin_line(buf)?;
+        buf.write_str("</html>")?;
+        f.end_line(buf)
+    }
+}
+
+impl Display for Document {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut formatter = TagFormatter::default();
+        self.format(&mut formatter, f)
+    }
+}
+
+/// a tag that is moved into `<head>` of the [`Document`] being rendered,
+/// so components deep in the tree can require their stylesheets or scripts
+///
+/// renders nothing outside a `Document`, identical entries are only written once
+///
+/// ```
+/// use rtml::{document::in_head, prop, tags::*, Document};
+///
+/// let card = div((
+///     in_head(link(prop! { rel = "stylesheet", href = "card.css" })),
+///     p("card"),
+/// ));
+/// let page = Document::new().body(body((card, p("footer"))));
+/// let html = page.to_string();
+/// let head_end = html.find("</head>").unwrap();
+/// assert!(html.find("card.css").unwrap() < head_end);
+/// ```
+pub struct InHead(pub Box<dyn Tag>);
+
+pub fn in_head<T: Tag + 'static>(tag: T) -> InHead {
+    InHead(Box::new(tag))
+}
+
+impl Tag for InHead {
+    fn name(&self) -> &str {
+        ""
+    }
+
+    fn format(&self, f: &mut TagFormatter, _buf: &mut dyn Write) -> std::fmt::Result {
+        let mut entry = String::new();
+        TagFormatter::compact().render(self.0.as_ref(), &mut entry)?;
+        f.push_head(entry);
+        Ok(())
+    }
+
+    fn as_element(&self) -> Option<&UnitTag> {
+        self.0.as_element()
+    }
+
+    fn as_element_mut(&mut self) -> Option<&mut UnitTag> {
+        self.0.as_element_mut()
+    }
+
+    fn permits(&self, child: &dyn Tag) -> bool {
+        self.0.permits(child)
+    }
+}
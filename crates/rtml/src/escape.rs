@@ -0,0 +1,145 @@
+use std::fmt::Write;
+
+/// where a piece of text is going to be written, decides how it is escaped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextContext {
+    /// normal element content, `&`, `<` and `>` are escaped
+    #[default]
+    Text,
+    /// content of a raw text element such as `script` or `style`,
+    /// entities are not decoded by browsers there, so only the closing tag sequence is broken up
+    RawText(&'static str),
+}
+
+/// escape text for html element content
+///
+/// ```
+/// let mut buf = String::new();
+/// rtml::escape::escape_text("a < b & c", &mut buf).unwrap();
+/// assert_eq!(buf, "a &lt; b &amp; c");
+/// ```
+pub fn escape_text<W: Write + ?Sized>(src: &str, buf: &mut W) -> std::fmt::Result {
+    let mut last = 0;
+    for (idx, c) in src.char_indices() {
+        let replace = match c {
+            '&' => "&amp;",
+            '<' => "&lt;",
+            '>' => "&gt;",
+            _ => continue,
+        };
+        buf.write_str(&src[last..idx])?;
+        buf.write_str(replace)?;
+        last = idx + 1;
+    }
+    buf.write_str(&src[last..])
+}
+
+/// escape text for a double quoted attribute value
+///
+/// ```
+/// let mut buf = String::new();
+/// rtml::escape::escape_attr(r#"say "hi" & <bye>"#, &mut buf).unwrap();
+/// assert_eq!(buf, "say &quot;hi&quot; &amp; &lt;bye&gt;");
+/// ```
+pub fn escape_attr<W: Write + ?Sized>(src: &str, buf: &mut W) -> std::fmt::Result {
+    let mut last = 0;
+    for (idx, c) in src.char_indices() {
+        let replace = match c {
+            '&' => "&amp;",
+            '"' => "&quot;",
+            '<' => "&lt;",
+            '>' => "&gt;",
+            _ => continue,
+        };
+        buf.write_str(&src[last..idx])?;
+        buf.write_str(replace)?;
+        last = idx + 1;
+    }
+    buf.write_str(&src[last..])
+}
+
+/// escape text placed inside raw text element `tag`, e.g. `script` or `style`
+///
+/// any `</tag` (case insensitive) is written as `<\/tag` and `<!--` as `<\!--`,
+/// both are still valid js and css, but no longer end the element early
+///
+/// ```
+/// let mut buf = String::new();
+/// rtml::escape::escape_raw_text("script", r#"let s = "</SCRIPT>";"#, &mut buf).unwrap();
+/// assert_eq!(buf, r#"let s = "<\/SCRIPT>";"#);
+/// ```
+pub fn escape_raw_text<W: Write + ?Sized>(tag: &str, src: &str, buf: &mut W) -> std::fmt::Result {
+    let bytes = src.as_bytes();
+    let mut last = 0;
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] == b'<' {
+            let rest = &bytes[idx + 1..];
+            let close_tag = rest.first() == Some(&b'/')
+                && rest.len() > tag.len()
+                && rest[1..=tag.len()].eq_ignore_ascii_case(tag.as_bytes());
+            if close_tag || rest.starts_with(b"!--") {
+                buf.write_str(&src[last..=idx])?;
+                buf.write_char('\\')?;
+                last = idx + 1;
+            }
+        }
+        idx += 1;
+    }
+    buf.write_str(&src[last..])
+}
+
+/// escape the text of a comment, so it can not end the comment early
+///
+/// `--` is written as `- -`, and a leading `>` or `->` and a trailing `-` are padded
+/// with a space, so neither `-->`, `--!>` nor `<!--` can appear in the output
+///
+/// ```
+/// let mut buf = String::new();
+/// rtml::escape::escape_comment("--> <script>alert(1)</script> <!--", &mut buf).unwrap();
+/// assert_eq!(buf, "- -> <script>alert(1)</script> <!- - ");
+///
+/// let mut buf = String::new();
+/// rtml::escape::escape_comment("><b>", &mut buf).unwrap();
+/// assert_eq!(buf, " ><b>");
+/// ```
+pub fn escape_comment<W: Write + ?Sized>(src: &str, buf: &mut W) -> std::fmt::Result {
+    if src.starts_with('>') || src.starts_with("->") {
+        buf.write_char(' ')?;
+    }
+    let mut last_dash = false;
+    for c in src.chars() {
+        if c == '-' && last_dash {
+            buf.write_char(' ')?;
+        }
+        buf.write_char(c)?;
+        last_dash = c == '-';
+    }
+    if last_dash {
+        buf.write_char(' ')?;
+    }
+    Ok(())
+}
+
+/// escape the text of a doctype, which ends at the first `>`, so it is cut there
+///
+/// ```
+/// let mut buf = String::new();
+/// rtml::escape::escape_doctype("html><script>", &mut buf).unwrap();
+/// assert_eq!(buf, "html");
+/// ```
+pub fn escape_doctype<W: Write + ?Sized>(src: &str, buf: &mut W) -> std::fmt::Result {
+    buf.write_str(src.split('>').next().unwrap_or_default())
+}
+
+/// escape `src` according to `ctx`
+pub fn escape<W: Write + ?Sized>(ctx: TextContext, src: &str, buf: &mut W) -> std::fmt::Result {
+    match ctx {
+        TextContext::Text => escape_text(src, buf),
+        TextContext::RawText(tag) => escape_raw_text(tag, src, buf),
+    }
+}
+
+/// elements whose content is raw text
+pub const RAW_TEXT_ELEMENTS: [&str; 6] =
+    ["iframe", "noembed", "noframes", "script", "style", "xmp"];
@@ -0,0 +1,596 @@
+use std::{borrow::Cow, fmt::Write, sync::Arc};
+
+use crate::{
+    escape::{escape, escape_attr, TextContext, RAW_TEXT_ELEMENTS},
+    style::{scoped_css, Stylesheet},
+    tags::builtin_name,
+    Tag,
+};
+
+#[derive(Debug, Clone)]
+pub struct TagFormatter {
+    pub tab_size: usize,
+    pub indent: usize,
+    pub max_width: usize,
+    pub newline_on_prop: bool,
+    pub line_sep: &'static str,
+    /// escaping context of text currently being written
+    pub context: TextContext,
+    /// render props, styles and handlers sorted by name instead of insertion order,
+    /// useful for canonical output
+    pub sort_attrs: bool,
+    /// collapse runs of whitespace in text into a single space,
+    /// content of `pre`, `textarea`, `script` and `style` is kept as is
+    pub collapse_whitespace: bool,
+    /// omit end tags the html spec allows to omit, such as `</li>` followed by another `<li>`
+    pub omit_optional_end_tags: bool,
+    /// write attribute values without quotes when the html spec allows
+    pub unquoted_attrs: bool,
+    /// whether whitespace of the text currently being written is significant
+    pub preserve_whitespace: bool,
+    /// whether current content is in inline formatting context,
+    /// which is written without indentation and newlines
+    pub inline: bool,
+    /// tag name of the parent of the tag being formatted
+    pub parent: Cow<'static, str>,
+    /// name of the next sibling of the tag being formatted, `None` if it is the last child
+    pub next_sibling: Option<Cow<'static, str>>,
+    /// rendered `<head>` entries contributed from the body, see [`crate::document::in_head`]
+    pub head: Vec<String>,
+    /// js function definitions used by event handlers, written into one `<script>` by [`crate::Document`]
+    pub scripts: Vec<String>,
+    /// scoped stylesheets used by rendered tags, written into one `<style>` by [`crate::Document`]
+    pub styles: Vec<Arc<Stylesheet>>,
+}
+
+impl Default for TagFormatter {
+    fn default() -> Self {
+        Self {
+            tab_size: 4,
+            indent: 0,
+            max_width: 120,
+            newline_on_prop: false,
+            line_sep: "\n",
+            context: TextContext::Text,
+            sort_attrs: false,
+            collapse_whitespace: false,
+            omit_optional_end_tags: false,
+            unquoted_attrs: false,
+            preserve_whitespace: false,
+            inline: false,
+            parent: Cow::Borrowed(""),
+            next_sibling: None,
+            head: vec![],
+            scripts: vec![],
+            styles: vec![],
+        }
+    }
+}
+
+/// elements that have no end tag and can not have any children
+pub const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// whether `tag` is a void element
+pub fn is_void(tag: &str) -> bool {
+    VOID_ELEMENTS.contains(&tag)
+}
+
+/// elements whose content whitespace is rendered by browsers
+pub const PRESERVE_WHITESPACE_ELEMENTS: [&str; 2] = ["pre", "textarea"];
+
+/// elements laid out inline by browsers, whitespace around them is visible
+pub const INLINE_ELEMENTS: &[&str] = &[
+    "a", "abbr", "audio", "b", "bdi", "bdo", "br", "button", "canvas", "cite", "code", "data",
+    "del", "dfn", "em", "embed", "i", "iframe", "img", "input", "ins", "kbd", "label", "map",
+    "mark", "meter", "object", "output", "picture", "progress", "q", "rp", "rt", "ruby", "s",
+    "samp", "select", "slot", "small", "span", "strong", "sub", "sup", "textarea", "time", "u",
+    "var", "video", "wbr",
+];
+
+/// whether node named `name` takes part in inline formatting context, text always does,
+/// comments, raw html and doctypes are neutral and do not make their parent inline
+///
+/// ```
+/// use rtml::{tags::*, Comment};
+///
+/// let page = div((Comment::new(" nav "), div("a"), div("b")));
+/// assert_eq!(page.to_string(), "<div>\n    <!-- nav -->\n    <div>a</div>\n    <div>b</div>\n</div>\n");
+/// ```
+pub fn is_inline_level(name: &str) -> bool {
+    name == "#text" || INLINE_ELEMENTS.contains(&name)
+}
+
+/// elements that close an open `p` element, so `</p>` can be omitted before them
+pub(crate) const CLOSE_P_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "search",
+    "section",
+    "table",
+    "ul",
+];
+
+/// whether end tag of `tag` can be omitted, according to
+/// [optional tags](https://html.spec.whatwg.org/multipage/syntax.html#optional-tags)
+///
+/// `next` is the name of next sibling, `None` if `tag` is the last child of `parent`
+///
+/// ```
+/// use rtml::format::can_omit_end_tag;
+///
+/// assert!(can_omit_end_tag("p", None, "div"));
+/// assert!(!can_omit_end_tag("p", None, "a"));
+/// assert!(!can_omit_end_tag("p", None, "my-card"));
+/// ```
+pub fn can_omit_end_tag(tag: &str, next: Option<&str>, parent: &str) -> bool {
+    let next_in = |names: &[&str]| next.is_none_or(|n| names.contains(&n));
+    match tag {
+        "html" | "body" => next.is_none(),
+        "head" | "colgroup" | "caption" => next.is_none_or(is_element),
+        "li" => next_in(&["li"]),
+        "dt" => next.is_some_and(|n| n == "dt" || n == "dd"),
+        "dd" => next_in(&["dt", "dd"]),
+        "rt" | "rp" => next_in(&["rt", "rp"]),
+        "optgroup" => next_in(&["optgroup", "hr"]),
+        "option" => next_in(&["option", "optgroup", "hr"]),
+        "thead" => next.is_some_and(|n| n == "tbody" || n == "tfoot"),
+        "tbody" => next_in(&["tbody", "tfoot"]),
+        "tfoot" => next.is_none(),
+        "tr" => next_in(&["tr"]),
+        "td" | "th" => next_in(&["td", "th"]),
+        "p" => match next {
+            Some(n) => CLOSE_P_ELEMENTS.contains(&n),
+            // autonomous custom elements keep the `</p>` of their last child, as `a` and the others do
+            None => {
+                !parent.contains('-')
+                    && !matches!(
+                        parent,
+                        "a" | "audio" | "del" | "ins" | "map" | "noscript" | "video"
+                    )
+            }
+        },
+        _ => false,
+    }
+}
+
+/// node names that are not elements, see [`is_element`]
+const NODE_NAMES: &[&str] = &["", "#text", "#raw", "#comment", "#doctype", "#stylesheet"];
+
+/// `name` as a `Cow`, borrowed for built in elements and other nodes, so only custom names allocate
+pub(crate) fn static_name(name: &str) -> Cow<'static, str> {
+    if let Some(name) = builtin_name(name) {
+        return Cow::Borrowed(name);
+    }
+    match NODE_NAMES.iter().find(|n| **n == name) {
+        Some(name) => Cow::Borrowed(name),
+        None => Cow::Owned(name.to_string()),
+    }
+}
+
+/// text nodes are named `#text`, raw html `#raw`, nodes rendering nothing have empty name
+fn is_element(name: &str) -> bool {
+    !name.is_empty() && !name.starts_with('#')
+}
+
+/// attributes whose presence is their value, written bare when empty
+pub const BOOLEAN_ATTRIBUTES: &[&str] = &[
+    "allowfullscreen",
+    "async",
+    "autofocus",
+    "autoplay",
+    "checked",
+    "controls",
+    "default",
+    "defer",
+    "disabled",
+    "formnovalidate",
+    "hidden",
+    "inert",
+    "ismap",
+    "itemscope",
+    "loop",
+    "multiple",
+    "muted",
+    "nomodule",
+    "novalidate",
+    "open",
+    "playsinline",
+    "readonly",
+    "required",
+    "reversed",
+    "selected",
+];
+
+/// whether `name` is in [`BOOLEAN_ATTRIBUTES`], names are case insensitive
+pub fn is_boolean_attribute(name: &str) -> bool {
+    BOOLEAN_ATTRIBUTES.iter().any(|attr| attr.eq_ignore_ascii_case(name))
+}
+
+/// whether attribute value can be written without quotes
+fn can_unquote(val: &str) -> bool {
+    !val.is_empty()
+        && !val
+            .chars()
+            .any(|c| c.is_ascii_whitespace() || matches!(c, '"' | '\'' | '=' | '<' | '>' | '`'))
+}
+
+impl TagFormatter {
+    /// formatter for production output, no indentation, no newlines and insignificant whitespace collapsed
+    ///
+    /// ```
+    /// use rtml::tags::*;
+    ///
+    /// let page = ul((li("a   b"), li("c")));
+    /// let mut out = String::new();
+    /// TagFormatter::compact().render(&page, &mut out).unwrap();
+    /// assert_eq!(out, "<ul><li>a b</li><li>c</li></ul>");
+    /// ```
+    pub fn compact() -> Self {
+        Self {
+            tab_size: 0,
+            line_sep: "",
+            collapse_whitespace: true,
+            ..Default::default()
+        }
+    }
+
+    /// compact formatter which also omits optional end tags and unnecessary attribute quotes
+    ///
+    /// ```
+    /// use rtml::{prop, tags::*};
+    ///
+    /// let page = ul((prop! { class = "list" }, (li("a"), li("b"))));
+    /// let mut out = String::new();
+    /// TagFormatter::minified().render(&page, &mut out).unwrap();
+    /// assert_eq!(out, "<ul class=list><li>a<li>b</ul>");
+    /// ```
+    pub fn minified() -> Self {
+        Self {
+            omit_optional_end_tags: true,
+            unquoted_attrs: true,
+            ..Self::compact()
+        }
+    }
+
+    /// formatter writing nodes exactly as they are, no indentation, no newlines and no whitespace
+    /// collapsing, so html parsed by [`crate::parse`] is written back unchanged
+    ///
+    /// ```
+    /// use rtml::{parse::parse, tags::*};
+    ///
+    /// let src = "<ul>\n  <li>a   b</li>\n</ul>";
+    /// let mut out = String::new();
+    /// for node in parse(src).0 {
+    ///     TagFormatter::exact().render(node.as_ref(), &mut out).unwrap();
+    /// }
+    /// assert_eq!(out, src);
+    /// ```
+    pub fn exact() -> Self {
+        Self {
+            tab_size: 0,
+            line_sep: "",
+            ..Default::default()
+        }
+    }
+
+    pub fn pad_size(&self) -> usize {
+        self.indent * self.tab_size
+    }
+
+    /// add a rendered `<head>` entry, identical entries are only kept once
+    pub fn push_head(&mut self, entry: String) {
+        if !self.head.contains(&entry) {
+            self.head.push(entry);
+        }
+    }
+
+    /// add a js function definition, identical definitions are only kept once
+    pub fn push_script(&mut self, definition: String) {
+        if !self.scripts.contains(&definition) {
+            self.scripts.push(definition);
+        }
+    }
+
+    /// add a scoped stylesheet, a stylesheet already added is only kept once
+    pub fn push_style(&mut self, sheet: &Arc<Stylesheet>) {
+        if !self.styles.iter().any(|s| Arc::ptr_eq(s, sheet)) {
+            self.styles.push(sheet.clone());
+        }
+    }
+
+    /// whether output is pretty printed at current position, so newlines can be inserted for layout
+    pub fn is_pretty(&self) -> bool {
+        !self.inline && !self.line_sep.is_empty()
+    }
+
+    /// write indentation, unless in inline formatting context
+    pub fn begin_line(&self, buf: &mut dyn Write) -> std::fmt::Result {
+        if self.inline {
+            return Ok(());
+        }
+        let pad = self.pad_size();
+        write!(buf, "{:pad$}", "")
+    }
+
+    /// write line separator, unless in inline formatting context
+    pub fn end_line(&self, buf: &mut dyn Write) -> std::fmt::Result {
+        if self.inline {
+            return Ok(());
+        }
+        buf.write_str(self.line_sep)
+    }
+
+    /// write text content, escaped according to current context
+    pub fn write_text(&self, buf: &mut dyn Write, text: &str) -> std::fmt::Result {
+        if !self.collapse_whitespace
+            || self.preserve_whitespace
+            || self.context != TextContext::Text
+        {
+            return escape(self.context, text, buf);
+        }
+        let mut last_space = false;
+        let mut start = 0;
+        for (idx, c) in text.char_indices() {
+            if c.is_ascii_whitespace() {
+                if !last_space {
+                    escape(self.context, &text[start..idx], buf)?;
+                    buf.write_char(' ')?;
+                }
+                last_space = true;
+                start = idx + 1;
+            } else {
+                last_space = false;
+            }
+        }
+        escape(self.context, &text[start..], buf)
+    }
+
+    /// write an attribute, `name` only if it is a boolean attribute and value is empty
+    ///
+    /// ```
+    /// use rtml::{prop, tags::*};
+    ///
+    /// let field = input(prop! { value = "", disabled = "" });
+    /// assert_eq!(field.to_string().trim_end(), r#"<input value="" disabled>"#);
+    /// ```
+    pub fn write_attr(&self, buf: &mut dyn Write, name: &str, val: &str) -> std::fmt::Result {
+        buf.write_str(name)?;
+        if val.is_empty() && is_boolean_attribute(name) {
+            return Ok(());
+        }
+        if self.unquoted_attrs && can_unquote(val) {
+            buf.write_char('=')?;
+            escape_attr(val, buf)
+        } else {
+            buf.write_str("=\"")?;
+            escape_attr(val, buf)?;
+            buf.write_char('"')
+        }
+    }
+
+    /// render `tag` into any `fmt::Write` sink, such as `String` or `fmt::Formatter`
+    pub fn render<T: Tag + ?Sized, W: Write>(&mut self, tag: &T, buf: &mut W) -> std::fmt::Result {
+        tag.format(self, buf)
+    }
+
+    /// render `tag` on its own, outside a [`crate::Document`], followed by a `<style>` with
+    /// the scoped stylesheets and a `<script>` with the js functions it uses,
+    /// which a document writes into its head instead
+    pub fn render_fragment<T: Tag + ?Sized, W: Write>(
+        &mut self,
+        tag: &T,
+        buf: &mut W,
+    ) -> std::fmt::Result {
+        let styles = std::mem::take(&mut self.styles);
+        let scripts = std::mem::take(&mut self.scripts);
+        tag.format(self, buf)?;
+        let sheets = std::mem::replace(&mut self.styles, styles);
+        let functions = std::mem::replace(&mut self.scripts, scripts);
+        if !sheets.is_empty() {
+            crate::tags::style(scoped_css(&sheets)?).format(self, buf)?;
+        }
+        if !functions.is_empty() {
+            crate::tags::script(functions.join(self.line_sep)).format(self, buf)?;
+        }
+        Ok(())
+    }
+
+    /// render `tag` into any `io::Write` sink, such as file, socket or compress encoder,
+    /// without building the whole document in memory
+    ///
+    /// ```no_run
+    /// use rtml::tags::*;
+    ///
+    /// let page = html(body(h1("hello")));
+    /// let file = std::fs::File::create("index.html").unwrap();
+    /// TagFormatter::default().render_io(&page, std::io::BufWriter::new(file)).unwrap();
+    /// ```
+    pub fn render_io<T: Tag + ?Sized, W: std::io::Write>(
+        &mut self,
+        tag: &T,
+        writer: W,
+    ) -> std::io::Result<()> {
+        let mut adapter = IoAdapter {
+            inner: writer,
+            error: None,
+        };
+        match tag.format(self, &mut adapter) {
+            Ok(()) => adapter.inner.flush(),
+            Err(_) => Err(adapter
+                .error
+                .unwrap_or_else(|| std::io::Error::other("formatter error"))),
+        }
+    }
+}
+
+/// write into a string, fail once more than `remain` chars or a newline is written,
+/// used to check whether some content fits in one line
+pub(crate) struct Budget<'a> {
+    buf: &'a mut String,
+    remain: usize,
+}
+
+impl<'a> Budget<'a> {
+    pub(crate) fn new(buf: &'a mut String, remain: usize) -> Self {
+        Self { buf, remain }
+    }
+}
+
+impl Write for Budget<'_> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        let len = s.chars().count();
+        if len > self.remain || s.contains('\n') {
+            return Err(std::fmt::Error);
+        }
+        self.remain -= len;
+        self.buf.push_str(s);
+        Ok(())
+    }
+}
+
+/// write a newline before content if it starts with one,
+/// browsers drop a newline right after `<pre>` or `<textarea>`
+pub(crate) struct LeadingNewline<'a> {
+    inner: &'a mut dyn Write,
+    started: bool,
+}
+
+impl<'a> LeadingNewline<'a> {
+    pub(crate) fn new(inner: &'a mut dyn Write) -> Self {
+        Self {
+            inner,
+            started: false,
+        }
+    }
+}
+
+impl Write for LeadingNewline<'_> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        if !self.started && !s.is_empty() {
+            self.started = true;
+            if s.starts_with('\n') {
+                self.inner.write_char('\n')?;
+            }
+        }
+        self.inner.write_str(s)
+    }
+}
+
+/// split rendered inline content at whitespace that browsers collapse,
+/// whitespace inside tags and whitespace sensitive elements is kept
+fn inline_words(content: &str) -> Vec<&str> {
+    let bytes = content.as_bytes();
+    let mut words = vec![];
+    let mut start = 0;
+    let mut in_tag = false;
+    let mut keep_depth = 0usize;
+    let mut idx = 0;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'<' => {
+                in_tag = true;
+                let closing = bytes.get(idx + 1) == Some(&b'/');
+                let name_start = if closing { idx + 2 } else { idx + 1 };
+                let name_end = bytes[name_start..]
+                    .iter()
+                    .position(|b| !b.is_ascii_alphanumeric())
+                    .map_or(bytes.len(), |p| name_start + p);
+                let name = &content[name_start..name_end];
+                if PRESERVE_WHITESPACE_ELEMENTS.contains(&name) || RAW_TEXT_ELEMENTS.contains(&name)
+                {
+                    if closing {
+                        keep_depth = keep_depth.saturating_sub(1);
+                    } else {
+                        keep_depth += 1;
+                    }
+                }
+            }
+            b'>' => in_tag = false,
+            b if b.is_ascii_whitespace() && !in_tag && keep_depth == 0 => {
+                if start < idx {
+                    words.push(&content[start..idx]);
+                }
+                start = idx + 1;
+            }
+            _ => {}
+        }
+        idx += 1;
+    }
+    if start < bytes.len() {
+        words.push(&content[start..]);
+    }
+    words
+}
+
+/// write rendered inline content as indented lines no wider than `max_width` where possible
+pub(crate) fn wrap_inline(
+    f: &TagFormatter,
+    content: &str,
+    buf: &mut dyn Write,
+) -> std::fmt::Result {
+    let width = f.max_width.saturating_sub(f.pad_size());
+    let mut line_len = 0;
+    for word in inline_words(content) {
+        let len = word.chars().count();
+        if line_len == 0 {
+            f.begin_line(buf)?;
+            line_len = len;
+        } else if line_len + 1 + len <= width {
+            buf.write_char(' ')?;
+            line_len += 1 + len;
+        } else {
+            f.end_line(buf)?;
+            f.begin_line(buf)?;
+            line_len = len;
+        }
+        buf.write_str(word)?;
+    }
+    if line_len > 0 {
+        f.end_line(buf)?;
+    }
+    Ok(())
+}
+
+/// bridge `io::Write` to `fmt::Write`, keep the real io error which `fmt::Error` can not carry
+struct IoAdapter<W: std::io::Write> {
+    inner: W,
+    error: Option<std::io::Error>,
+}
+
+impl<W: std::io::Write> Write for IoAdapter<W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            std::fmt::Error
+        })
+    }
+}
@@ -1,16 +1,106 @@
-use tags::TagFormatter;
+use format::TagFormatter;
 
+/// typed attributes of built in tags
+pub mod attrs;
 mod basic_impl;
+/// content categories and content models of html elements
+pub mod content;
+/// complete html document
+pub mod document;
+/// html escaping
+pub mod escape;
+/// tag formatter and rendering options
+pub mod format;
+/// html parser building rtml trees
+pub mod parse;
 /// js relative
 pub mod script;
-/// css relative
+/// typed css values, known css properties and stylesheets
 pub mod style;
 /// built in standard html tags
 pub mod tags;
+/// walking, querying and modifying built trees
+pub mod tree;
+/// runtime html conformance checks
+pub mod validate;
+
+pub use document::Document;
+
+/// turn a function into a reusable component
+///
+/// `fn card(..)` gets a `Card` tag, built with `Card::builder()`, a setter for each param
+/// and `build()`, which runs the function once. setters accept `impl Into<T>`,
+/// params are required unless marked `#[prop(optional)]` (an `Option<T>`, setter takes `T`),
+/// `#[prop(default)]` or `#[prop(default = expr)]`, and param `children` is the children slot.
+/// a missing required prop is a compile error, `build()` is not available until all are set
+///
+/// components can be used in [`html!`] by name, like built in tags.
+/// generated code refers to `::rtml`, write `#[component(crate = path)]` if rtml is
+/// renamed in `Cargo.toml` or used through a re-export
+///
+/// ```
+/// use rtml::{component, html, tags::*, Children};
+///
+/// #[component]
+/// fn card(
+///     title: String,
+///     #[prop(optional)] subtitle: Option<String>,
+///     #[prop(default = 2)] level: u8,
+///     children: Children,
+/// ) -> Div {
+///     let heading = x((if level == 1 { "h1" } else { "h2" }, title));
+///     div((heading, subtitle.map(p).unwrap_or_else(|| p(())), div(children)))
+/// }
+///
+/// let a = Card::builder().title("a").level(1).children(p("content")).build();
+/// let b = html! {
+///     <Card title="b" subtitle="sub">
+///         <p>"content"</p>
+///     </Card>
+/// };
+/// assert!(a.to_string().contains("<h1>a</h1>"));
+/// assert!(b.to_string().contains("<h2>b</h2>"));
+/// ```
+///
+/// ```compile_fail
+/// use rtml::{component, tags::*};
+///
+/// #[component]
+/// fn card(title: String) -> Div {
+///     div(title)
+/// }
+///
+/// let card = Card::builder().build();
+/// ```
+///
+/// ```
+/// mod web {
+///     pub use rtml::*;
+/// }
+///
+/// use web::{component, tags::*};
+///
+/// #[component(crate = crate::web)]
+/// fn badge(text: String) -> Span {
+///     span(text)
+/// }
+///
+/// fn main() {
+///     assert_eq!(Badge::builder().text("new").build().to_string(), "<span>new</span>\n");
+/// }
+/// ```
+pub use rtml_macro::component;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::attrs::{PropValue, ViaAttrValue, ViaDisplay};
+    pub use rtml_macro::{html_impl, prop_impl, style_impl};
+}
 
 pub type InnerChildren = Vec<Box<dyn Tag>>;
 
 /// helper struct for easier input
+#[derive(Default)]
 pub struct Children(pub InnerChildren);
 
 impl Children {
@@ -18,14 +108,144 @@ impl Children {
     pub fn push<T: Tag + 'static>(&mut self, item: T) {
         self.0.push(Box::new(item))
     }
+
+    /// add everything converting into children, e.g. a tuple or a `Vec` of tags
+    pub fn append<C: Into<Children>>(&mut self, children: C) {
+        self.0.extend(children.into().0)
+    }
+}
+
+impl<T: Tag + 'static> FromIterator<T> for Children {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(iter.into_iter().map(|c| Box::new(c) as Box<dyn Tag>).collect())
+    }
 }
 
 /// a trait represent html tags
+///
+/// `format` writes into any `fmt::Write`, so a tag can be rendered into a `String`,
+/// a `fmt::Formatter`, or an `io::Write` through [`TagFormatter::render_io`]
 pub trait Tag {
-    fn name(&self) -> &'static str;
-    fn format(&self, f: &mut TagFormatter, buf: &mut String) -> std::fmt::Result;
+    /// tag name, `#text` for text nodes
+    fn name(&self) -> &str;
+    fn format(&self, f: &mut TagFormatter, buf: &mut dyn std::fmt::Write) -> std::fmt::Result;
+
+    /// the element, for walking a tree, `None` for text and other nodes without children
+    fn as_element(&self) -> Option<&tags::UnitTag> {
+        None
+    }
+
+    /// the element, for modifying a tree after it is built, see [`tree`]
+    fn as_element_mut(&mut self) -> Option<&mut tags::UnitTag> {
+        None
+    }
+
+    /// for downcasting, `None` if the tag is not `'static`, see [`tree`]
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        None
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
+        None
+    }
+
+    /// content categories, everything not checked by default
+    fn categories(&self) -> &'static [content::Category] {
+        content::Category::UNCHECKED
+    }
+
+    /// whether `child` is permitted by the content model, anything by default
+    fn permits(&self, _child: &dyn Tag) -> bool {
+        true
+    }
 }
 
 /// represent empty children
 #[derive(Clone, Copy)]
 pub struct Kong;
+
+/// one of two tags, for branches producing different tags
+///
+/// `Option`, `Result`, `Rc`, `Arc`, `Box<dyn Tag>` and iterator adaptors such as `map` and `filter`
+/// can be children too, a `None` renders nothing
+///
+/// ```
+/// use rtml::{tags::*, Either};
+///
+/// let logged_in = true;
+/// let items = ["home", "about"];
+/// let nav = div((
+///     if logged_in { Either::Left(a("logout")) } else { Either::Right(button("login")) },
+///     ul(items.iter().map(|item| li(*item))),
+///     (!logged_in).then(|| p("welcome")),
+/// ));
+/// assert_eq!(
+///     nav.to_string(),
+///     "<div><a>logout</a><ul><li>home</li><li>about</li></ul></div>\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+/// trusted, pre-rendered html fragment, written out as is without any escaping
+///
+/// only wrap content you trust, such as markdown output or svg icons shipped with the app
+///
+/// ```
+/// use rtml::{tags::*, Raw};
+///
+/// let page = div(Raw::new("<b>bold</b>"));
+/// assert!(page.to_string().contains("<b>bold</b>"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Raw(pub String);
+
+impl Raw {
+    pub fn new<S: Into<String>>(html: S) -> Self {
+        Self(html.into())
+    }
+}
+
+/// html comment, `<!--text-->`, text that could end the comment early is broken up
+/// when written, see [`escape::escape_comment`]
+///
+/// ```
+/// use rtml::{tags::*, Comment};
+///
+/// let page = div((Comment::new(" nav "), "text"));
+/// assert_eq!(page.to_string(), "<div><!-- nav -->text</div>\n");
+///
+/// let evil = div(Comment::new("--> <script>alert(1)</script> <!--"));
+/// assert_eq!(evil.to_string(), "<div><!--- -> <script>alert(1)</script> <!- - --></div>\n");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Comment(pub String);
+
+impl Comment {
+    pub fn new<S: Into<String>>(text: S) -> Self {
+        Self(text.into())
+    }
+}
+
+/// doctype declaration, `<!DOCTYPE html>` for `Doctype::new("html")`,
+/// usually kept from parsed pages, [`Document`] writes its own
+///
+/// ```
+/// use rtml::Doctype;
+///
+/// assert!(Doctype::new("html").is_some());
+/// assert!(Doctype::new("html><script>alert(1)</script").is_none());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Doctype(pub String);
+
+impl Doctype {
+    /// `None` if `text` contains `>`, which would end the doctype early
+    pub fn new<S: Into<String>>(text: S) -> Option<Self> {
+        let text = text.into();
+        (!text.contains('>')).then_some(Self(text))
+    }
+}
@@ -0,0 +1,1060 @@
+//! parse existing html, such as legacy templates, cms content or email bodies,
+//! into [`UnitTag`] trees, which can be walked, modified and rendered again
+//!
+//! like browsers, the parser never fails. misnested and unclosed tags are fixed up
+//! the way the [html spec](https://html.spec.whatwg.org/multipage/parsing.html) does for
+//! the common cases: optional end tags are implied, stray end tags are ignored, formatting
+//! elements closed too early are reopened, and rows and cells get their `tbody` and `tr`
+//!
+//! - attribute values and text are decoded, `&amp;` becomes `&`
+//! - attributes, `style` and `on*` included, are kept as props in source order with their text as is
+//! - `style` is also split into [`TagStyle`](crate::tags::TagStyle) declarations if it is plain declarations,
+//!   and `on*` copied into [`TagHandler`](crate::tags::TagHandler) entries, changes to them are
+//!   written in place of the original attribute, which is written as is while they are unchanged
+//! - comments and doctype are kept as [`Comment`] and [`Doctype`]
+//! - whitespace is kept as is, render with [`TagFormatter::exact`](crate::tags::TagFormatter::exact)
+//!   to get the same html back
+//!
+//! ```
+//! use rtml::{parse::parse, tags::*};
+//!
+//! let nodes = parse(r#"<ul class=nav><li>a &amp; b<li style="color: red">c</ul>"#);
+//! let list = nodes.0[0].as_element().unwrap();
+//! assert_eq!(list.get_attr("class"), Some("nav"));
+//! assert_eq!(list.select("li").len(), 2);
+//! assert_eq!(list.select("li")[1].get_attr("style"), Some("color: red"));
+//! assert_eq!(list.select("li")[1].style.0.get("color").unwrap(), "red");
+//!
+//! let mut out = String::new();
+//! TagFormatter::exact().render(list, &mut out).unwrap();
+//! assert_eq!(out, r#"<ul class="nav"><li>a &amp; b</li><li style="color: red">c</li></ul>"#);
+//!
+//! let mut nodes = parse(r#"<p style="color: red" onclick="go()" id="a">x</p>"#);
+//! let para = nodes.0[0].as_element_mut().unwrap();
+//! para.style.0.insert("margin", "0");
+//! para.on.insert("click", "stop()");
+//! let mut out = String::new();
+//! TagFormatter::exact().render(para, &mut out).unwrap();
+//! assert_eq!(out, r#"<p style="color: red; margin: 0; " onclick="stop()" id="a">x</p>"#);
+//! ```
+
+use crate::escape::RAW_TEXT_ELEMENTS;
+use crate::format::{is_void, static_name, CLOSE_P_ELEMENTS, INLINE_ELEMENTS};
+use crate::tags::{OrderedMap, TagStyle, UnitTag};
+use crate::{Children, Comment, Doctype, Tag};
+
+/// parse an html fragment into nodes, elements, text, comments and doctype as they appear
+///
+/// `html`, `head` and `body` are kept where they are written, use [`parse_document`] for whole pages
+///
+/// ```
+/// use rtml::{parse::parse, tags::*};
+///
+/// // `p` is closed by `div`, `b` is reopened for the text after it like browsers do
+/// let nodes = parse("<p><b>bold<div>block</div>");
+/// let html: String = nodes.0.iter().map(|n| {
+///     let mut out = String::new();
+///     TagFormatter::exact().render(n.as_ref(), &mut out).unwrap();
+///     out
+/// }).collect();
+/// assert_eq!(html, "<p><b>bold</b></p><div><b>block</b></div>");
+/// ```
+pub fn parse(html: &str) -> Children {
+    Builder::new(false).run(html)
+}
+
+/// parse a whole page, nodes before `<html>` such as [`Doctype`] and comments come first,
+/// then the `html` element, which always has `head` and `body`, implied if missing
+///
+/// ```
+/// use rtml::{parse::parse_document, tags::*, Doctype};
+///
+/// let nodes = parse_document("<!DOCTYPE html><title>home</title><p>hello");
+/// assert!(nodes.0[0].is::<Doctype>());
+/// let html = nodes.0[1].as_element().unwrap();
+/// assert_eq!(html.select("head > title").len(), 1);
+/// assert_eq!(html.select("body > p").len(), 1);
+/// ```
+pub fn parse_document(html: &str) -> Children {
+    Builder::new(true).run(html)
+}
+
+enum Token {
+    Doctype(String),
+    Comment(String),
+    Start {
+        name: String,
+        attrs: Vec<(String, String)>,
+        self_closing: bool,
+    },
+    End(String),
+    Text(String),
+}
+
+struct Tokenizer<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn next_token(&mut self) -> Option<Token> {
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return None;
+            }
+            // text runs until the next markup, a `<` not starting markup is text
+            let end = rest
+                .match_indices('<')
+                .map(|(idx, _)| idx)
+                .find(|idx| starts_markup(&rest[*idx..]))
+                .unwrap_or(rest.len());
+            if end > 0 {
+                self.pos += end;
+                return Some(Token::Text(decode(&rest[..end], false)));
+            }
+            if let Some(token) = self.markup() {
+                return Some(token);
+            }
+        }
+    }
+
+    /// markup at current position, `None` if it is ignored
+    fn markup(&mut self) -> Option<Token> {
+        let rest = self.rest();
+        if let Some(body) = rest.strip_prefix("<!--") {
+            // `<!-->` and `<!--->` are empty comments
+            for empty in [">", "->"] {
+                if body.starts_with(empty) {
+                    self.pos += 4 + empty.len();
+                    return Some(Token::Comment(String::new()));
+                }
+            }
+            let (text, len) = match body.find("-->") {
+                Some(end) => (&body[..end], end + 3),
+                None => (body, body.len()),
+            };
+            self.pos += 4 + len;
+            return Some(Token::Comment(text.to_string()));
+        }
+        if rest.starts_with("<!") || rest.starts_with("<?") {
+            let start = if rest.starts_with("<?") { 1 } else { 2 };
+            let text = self.until_gt(start);
+            return match text.get(..7) {
+                Some(doctype) if doctype.eq_ignore_ascii_case("doctype") => {
+                    Some(Token::Doctype(text[7..].trim().to_string()))
+                }
+                _ => Some(Token::Comment(text.to_string())),
+            };
+        }
+        if let Some(body) = rest.strip_prefix("</") {
+            return match body.chars().next() {
+                Some(c) if c.is_ascii_alphabetic() => {
+                    let len = body
+                        .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+                        .unwrap_or(body.len());
+                    let name = body[..len].to_string();
+                    // attributes of end tags are dropped
+                    self.until_gt(2);
+                    Some(Token::End(name))
+                }
+                Some('>') => {
+                    self.pos += 3;
+                    None
+                }
+                _ => Some(Token::Comment(self.until_gt(2).to_string())),
+            };
+        }
+        self.start_tag()
+    }
+
+    /// text from `start` bytes after current position until the next `>`, which is skipped
+    fn until_gt(&mut self, start: usize) -> &'a str {
+        let rest = &self.rest()[start..];
+        let (text, len) = match rest.find('>') {
+            Some(end) => (&rest[..end], end + 1),
+            None => (rest, rest.len()),
+        };
+        self.pos += start + len;
+        text
+    }
+
+    fn start_tag(&mut self) -> Option<Token> {
+        let src = self.src.as_bytes();
+        let mut pos = self.pos + 1;
+        let name_end = |pos: usize, stop: &[u8]| {
+            let mut end = pos;
+            while end < src.len() && !src[end].is_ascii_whitespace() && !stop.contains(&src[end]) {
+                end += 1;
+            }
+            end
+        };
+        let end = name_end(pos, b"/>");
+        let name = self.src[pos..end].to_string();
+        pos = end;
+        let mut attrs: Vec<(String, String)> = vec![];
+        let mut self_closing = false;
+        loop {
+            match src.get(pos) {
+                // a tag cut off by the end of input is dropped
+                None => {
+                    self.pos = src.len();
+                    return None;
+                }
+                Some(b'>') => break,
+                Some(b'/') => {
+                    self_closing = true;
+                    pos += 1;
+                    continue;
+                }
+                Some(c) if c.is_ascii_whitespace() => {
+                    pos += 1;
+                    continue;
+                }
+                _ => self_closing = false,
+            }
+            // the first character of a name may be `=`
+            let end = name_end(pos + 1, b"/>=");
+            let attr = &self.src[pos..end];
+            pos = end;
+            while src.get(pos).is_some_and(u8::is_ascii_whitespace) {
+                pos += 1;
+            }
+            let mut value = String::new();
+            if src.get(pos) == Some(&b'=') {
+                pos += 1;
+                while src.get(pos).is_some_and(u8::is_ascii_whitespace) {
+                    pos += 1;
+                }
+                let raw = match src.get(pos) {
+                    Some(quote @ (b'"' | b'\'')) => {
+                        let start = pos + 1;
+                        match self.src[start..].find(*quote as char) {
+                            Some(len) => {
+                                pos = start + len + 1;
+                                &self.src[start..start + len]
+                            }
+                            None => {
+                                self.pos = src.len();
+                                return None;
+                            }
+                        }
+                    }
+                    _ => {
+                        let start = pos;
+                        pos = name_end(pos, b">");
+                        &self.src[start..pos]
+                    }
+                };
+                value = decode(raw, true);
+            }
+            // later duplicates of an attribute are dropped
+            if !attrs
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case(attr))
+            {
+                attrs.push((attr.to_string(), value));
+            }
+        }
+        self.pos = pos + 1;
+        Some(Token::Start {
+            name,
+            attrs,
+            self_closing,
+        })
+    }
+
+    /// content of raw text element `name` up to its end tag, which is left for the next token
+    fn raw_text(&mut self, name: &str) -> &'a str {
+        let rest = self.rest();
+        let bytes = rest.as_bytes();
+        let end = rest
+            .match_indices("</")
+            .map(|(idx, _)| idx)
+            .find(|&idx| {
+                let after = idx + 2 + name.len();
+                bytes.len() >= after
+                    && bytes[idx + 2..after].eq_ignore_ascii_case(name.as_bytes())
+                    && bytes
+                        .get(after)
+                        .is_none_or(|c| c.is_ascii_whitespace() || matches!(c, b'/' | b'>'))
+            })
+            .unwrap_or(rest.len());
+        self.pos += end;
+        &rest[..end]
+    }
+}
+
+/// whether `src`, starting with `<`, starts a tag, comment or doctype
+fn starts_markup(src: &str) -> bool {
+    let mut chars = src.chars().skip(1);
+    match chars.next() {
+        Some('!' | '?' | '/') => true,
+        Some(c) => c.is_ascii_alphabetic(),
+        None => false,
+    }
+}
+
+/// named character references decoded by the parser, others are kept as written
+const ENTITIES: &[(&str, &str)] = &[
+    ("AElig", "Æ"),
+    ("Aacute", "Á"),
+    ("Agrave", "À"),
+    ("Auml", "Ä"),
+    ("Ccedil", "Ç"),
+    ("Dagger", "‡"),
+    ("Eacute", "É"),
+    ("NewLine", "\n"),
+    ("Omega", "Ω"),
+    ("Ouml", "Ö"),
+    ("Prime", "″"),
+    ("Tab", "\t"),
+    ("Uuml", "Ü"),
+    ("aacute", "á"),
+    ("acirc", "â"),
+    ("aelig", "æ"),
+    ("agrave", "à"),
+    ("alpha", "α"),
+    ("amp", "&"),
+    ("apos", "'"),
+    ("aring", "å"),
+    ("ast", "*"),
+    ("auml", "ä"),
+    ("bdquo", "„"),
+    ("beta", "β"),
+    ("bsol", "\\"),
+    ("bull", "•"),
+    ("ccedil", "ç"),
+    ("cent", "¢"),
+    ("check", "✓"),
+    ("clubs", "♣"),
+    ("colon", ":"),
+    ("comma", ","),
+    ("commat", "@"),
+    ("copy", "©"),
+    ("cross", "✗"),
+    ("curren", "¤"),
+    ("dagger", "†"),
+    ("darr", "↓"),
+    ("deg", "°"),
+    ("delta", "δ"),
+    ("diams", "♦"),
+    ("divide", "÷"),
+    ("dollar", "$"),
+    ("eacute", "é"),
+    ("ecirc", "ê"),
+    ("egrave", "è"),
+    ("emsp", "\u{2003}"),
+    ("ensp", "\u{2002}"),
+    ("equals", "="),
+    ("equiv", "≡"),
+    ("euro", "€"),
+    ("excl", "!"),
+    ("frac12", "½"),
+    ("frac14", "¼"),
+    ("frac34", "¾"),
+    ("gamma", "γ"),
+    ("ge", "≥"),
+    ("grave", "`"),
+    ("gt", ">"),
+    ("hArr", "⇔"),
+    ("harr", "↔"),
+    ("hearts", "♥"),
+    ("hellip", "…"),
+    ("iacute", "í"),
+    ("iexcl", "¡"),
+    ("infin", "∞"),
+    ("iquest", "¿"),
+    ("lArr", "⇐"),
+    ("lambda", "λ"),
+    ("laquo", "«"),
+    ("larr", "←"),
+    ("lcub", "{"),
+    ("ldquo", "“"),
+    ("le", "≤"),
+    ("lowbar", "_"),
+    ("lpar", "("),
+    ("lrm", "\u{200E}"),
+    ("lsaquo", "‹"),
+    ("lsqb", "["),
+    ("lsquo", "‘"),
+    ("lt", "<"),
+    ("mdash", "—"),
+    ("micro", "µ"),
+    ("middot", "·"),
+    ("minus", "−"),
+    ("mu", "μ"),
+    ("nbsp", "\u{A0}"),
+    ("ndash", "–"),
+    ("ne", "≠"),
+    ("not", "¬"),
+    ("ntilde", "ñ"),
+    ("num", "#"),
+    ("oacute", "ó"),
+    ("ocirc", "ô"),
+    ("omega", "ω"),
+    ("oslash", "ø"),
+    ("ouml", "ö"),
+    ("para", "¶"),
+    ("percnt", "%"),
+    ("period", "."),
+    ("permil", "‰"),
+    ("pi", "π"),
+    ("plusmn", "±"),
+    ("pound", "£"),
+    ("prime", "′"),
+    ("quest", "?"),
+    ("quot", "\""),
+    ("rArr", "⇒"),
+    ("raquo", "»"),
+    ("rarr", "→"),
+    ("rcub", "}"),
+    ("rdquo", "”"),
+    ("reg", "®"),
+    ("rlm", "\u{200F}"),
+    ("rpar", ")"),
+    ("rsaquo", "›"),
+    ("rsqb", "]"),
+    ("rsquo", "’"),
+    ("sbquo", "‚"),
+    ("sect", "§"),
+    ("semi", ";"),
+    ("shy", "\u{AD}"),
+    ("sigma", "σ"),
+    ("sol", "/"),
+    ("spades", "♠"),
+    ("sup1", "¹"),
+    ("sup2", "²"),
+    ("sup3", "³"),
+    ("szlig", "ß"),
+    ("theta", "θ"),
+    ("thinsp", "\u{2009}"),
+    ("times", "×"),
+    ("trade", "™"),
+    ("uacute", "ú"),
+    ("uarr", "↑"),
+    ("uuml", "ü"),
+    ("verbar", "|"),
+    ("yen", "¥"),
+    ("zwj", "\u{200D}"),
+    ("zwnj", "\u{200C}"),
+];
+
+/// references browsers also decode without the trailing `;`
+const LEGACY_ENTITIES: [&str; 7] = ["nbsp", "quot", "copy", "amp", "reg", "lt", "gt"];
+
+/// decode character references in text, or in an attribute value if `attr` is set
+fn decode(src: &str, attr: bool) -> String {
+    let mut out = String::with_capacity(src.len());
+    let mut rest = src;
+    while let Some(idx) = rest.find('&') {
+        out.push_str(&rest[..idx]);
+        rest = &rest[idx..];
+        let len = char_ref(rest, attr, &mut out).unwrap_or_else(|| {
+            out.push('&');
+            1
+        });
+        rest = &rest[len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// decode the reference at the start of `src` into `out`, returning its length
+fn char_ref(src: &str, attr: bool, out: &mut String) -> Option<usize> {
+    let body = &src[1..];
+    if let Some(num) = body.strip_prefix('#') {
+        let (digits, radix, prefix) = match num.strip_prefix(['x', 'X']) {
+            Some(hex) => (hex, 16, 3),
+            None => (num, 10, 2),
+        };
+        let len = digits
+            .find(|c: char| !c.is_digit(radix))
+            .unwrap_or(digits.len());
+        if len == 0 {
+            return None;
+        }
+        // out of range and surrogate code points become the replacement character
+        let c = u32::from_str_radix(&digits[..len], radix)
+            .ok()
+            .filter(|&code| code != 0)
+            .and_then(char::from_u32)
+            .unwrap_or('\u{FFFD}');
+        out.push(c);
+        let semi = digits[len..].starts_with(';') as usize;
+        return Some(prefix + len + semi);
+    }
+    let len = body
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(body.len());
+    let name = &body[..len];
+    if body[len..].starts_with(';') {
+        if let Ok(idx) = ENTITIES.binary_search_by(|(n, _)| n.cmp(&name)) {
+            out.push_str(ENTITIES[idx].1);
+            return Some(len + 2);
+        }
+    }
+    let legacy = LEGACY_ENTITIES
+        .iter()
+        .find(|legacy| name.starts_with(*legacy))?;
+    // in attribute values `&copy=1` is kept as written, it is likely part of a url
+    let next = body[legacy.len()..].chars().next();
+    if attr && next.is_some_and(|c| c.is_ascii_alphanumeric() || c == '=') {
+        return None;
+    }
+    let idx = ENTITIES.binary_search_by(|(n, _)| n.cmp(legacy)).ok()?;
+    out.push_str(ENTITIES[idx].1);
+    Some(legacy.len() + 1)
+}
+
+/// elements whose content is text up to their end tag, decoded or not
+const RCDATA: &[&str] = &["textarea", "title"];
+
+/// elements closed too early are reopened for the content after them
+const FORMATTING_ELEMENTS: &[&str] = &[
+    "a", "b", "big", "code", "em", "font", "i", "nobr", "s", "small", "strike", "strong", "tt", "u",
+];
+
+/// elements end tags do not look past, `</div>` in a table cell does not close a `div` around the table
+const SCOPE_BOUNDARIES: &[&str] = &[
+    "applet", "caption", "html", "marquee", "object", "table", "td", "template", "th",
+];
+
+/// elements which may stay in `head`
+const HEAD_ELEMENTS: &[&str] = &[
+    "base", "basefont", "bgsound", "link", "meta", "noscript", "script", "style", "template",
+    "title",
+];
+
+const TABLE_SECTIONS: &[&str] = &["tbody", "tfoot", "thead"];
+
+/// split a `style` attribute into declarations, `None` if it has anything else, such as comments
+///
+/// ```
+/// use rtml::parse::split_style;
+///
+/// let style = split_style("color: red; background: url(a;b.png)").unwrap();
+/// assert_eq!(style.get("background").unwrap(), "url(a;b.png)");
+/// assert!(split_style("color: red /* note */").is_none());
+/// ```
+pub fn split_style(css: &str) -> Option<OrderedMap> {
+    if css.contains("/*") {
+        return None;
+    }
+    let mut map = OrderedMap::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut start = 0;
+    for (idx, c) in css.char_indices().chain([(css.len(), ';')]) {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, ';') if depth == 0 => {
+                let decl = css[start..idx].trim();
+                start = idx + 1;
+                if decl.is_empty() {
+                    continue;
+                }
+                let (name, value) = decl.split_once(':')?;
+                let (name, value) = (name.trim(), value.trim());
+                let ident = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+                if name.is_empty()
+                    || !name.chars().all(ident)
+                    || value.is_empty()
+                    || map.contains_key(name)
+                {
+                    return None;
+                }
+                map.insert(name, value);
+            }
+            _ => {}
+        }
+    }
+    (!map.is_empty() && quote.is_none()).then_some(map)
+}
+
+/// insertion modes of a whole page, before `body` content starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Initial,
+    BeforeHead,
+    InHead,
+    AfterHead,
+    InBody,
+}
+
+struct Builder {
+    /// open elements, the first one is the root holding parsed nodes
+    stack: Vec<UnitTag>,
+    /// formatting elements closed by an end tag of their parent, reopened before following inline content
+    reopen: Vec<UnitTag>,
+    document: bool,
+    mode: Mode,
+    /// browsers drop a newline right after `<pre>`, `<listing>` or `<textarea>`
+    skip_newline: bool,
+}
+
+impl Builder {
+    fn new(document: bool) -> Self {
+        Self {
+            stack: vec![UnitTag::from(("", Children::default()))],
+            reopen: vec![],
+            document,
+            mode: if document {
+                Mode::Initial
+            } else {
+                Mode::InBody
+            },
+            skip_newline: false,
+        }
+    }
+
+    fn run(mut self, html: &str) -> Children {
+        // browsers normalize newlines before parsing
+        let html = html.replace("\r\n", "\n").replace(['\r', '\0'], "\n");
+        let mut tokenizer = Tokenizer { src: &html, pos: 0 };
+        while let Some(token) = tokenizer.next_token() {
+            self.process(token, &mut tokenizer);
+        }
+        while self.mode != Mode::InBody {
+            self.advance();
+        }
+        while self.stack.len() > 1 {
+            self.pop();
+        }
+        let root = self.stack.pop().unwrap();
+        Children(root.children)
+    }
+
+    fn current(&mut self) -> &mut UnitTag {
+        self.stack.last_mut().unwrap()
+    }
+
+    /// index of the innermost open element named `name`, not looking past `boundaries`
+    fn find(&self, name: &str, boundaries: &[&str]) -> Option<usize> {
+        for (idx, el) in self.stack.iter().enumerate().skip(1).rev() {
+            if el.tag.eq_ignore_ascii_case(name) {
+                return Some(idx);
+            }
+            if boundaries.contains(&&*el.tag) {
+                return None;
+            }
+        }
+        None
+    }
+
+    /// whether current node is inside `svg` or `math`, where names are case sensitive
+    fn in_foreign(&self) -> bool {
+        self.stack
+            .iter()
+            .rev()
+            .find_map(|el| match &*el.tag {
+                "svg" | "math" => Some(true),
+                _ if el.tag.eq_ignore_ascii_case("foreignObject") => Some(false),
+                _ => None,
+            })
+            .unwrap_or(false)
+    }
+
+    fn push(&mut self, el: UnitTag) {
+        self.stack.push(el);
+    }
+
+    /// close current node, adding it to its parent
+    fn pop(&mut self) {
+        let el = self.stack.pop().unwrap();
+        self.current().children.push(Box::new(el));
+    }
+
+    /// close open elements down to and including the one at `idx`
+    fn close(&mut self, idx: usize) {
+        let base = self.reopen.len();
+        while self.stack.len() > idx + 1 {
+            let el = self.stack.last().unwrap();
+            if FORMATTING_ELEMENTS.contains(&&*el.tag) {
+                let shell = UnitTag::new(el.tag.clone())
+                    .props(el.props.clone())
+                    .style(el.style.clone())
+                    .on(el.on.clone());
+                self.reopen.insert(base, shell);
+            }
+            self.pop();
+        }
+        self.pop();
+    }
+
+    fn close_named(&mut self, name: &str, boundaries: &[&str]) {
+        if let Some(idx) = self.find(name, boundaries) {
+            self.close(idx);
+        }
+    }
+
+    fn reconstruct(&mut self) {
+        let reopen = std::mem::take(&mut self.reopen);
+        self.stack.extend(reopen);
+    }
+
+    fn insert<T: Tag + 'static>(&mut self, node: T) {
+        self.current().children.push(Box::new(node));
+    }
+
+    /// add text to current node, joined with the text before it
+    fn insert_text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let last = self.current().children.last_mut();
+        if let Some(prev) = last.and_then(|c| c.as_any_mut()?.downcast_mut::<String>()) {
+            prev.push_str(text);
+            return;
+        }
+        self.insert(text.to_string());
+    }
+
+    /// implied structure of a whole page, for a token `process` does not handle in current mode
+    fn advance(&mut self) {
+        self.mode = match self.mode {
+            Mode::Initial => {
+                self.push(UnitTag::from(("html", Children::default())));
+                Mode::BeforeHead
+            }
+            Mode::BeforeHead => {
+                self.push(UnitTag::from(("head", Children::default())));
+                Mode::InHead
+            }
+            Mode::InHead => {
+                self.close_named("head", &[]);
+                Mode::AfterHead
+            }
+            Mode::AfterHead | Mode::InBody => {
+                self.push(UnitTag::from(("body", Children::default())));
+                Mode::InBody
+            }
+        };
+    }
+
+    fn process(&mut self, mut token: Token, tokenizer: &mut Tokenizer) {
+        while self.mode != Mode::InBody {
+            // comments and whitespace stay where they are before the body starts
+            match token {
+                Token::Comment(text) => return self.insert(Comment(text)),
+                Token::Doctype(text) if self.mode == Mode::Initial => {
+                    return self.insert(Doctype(text))
+                }
+                Token::Doctype(_) => return,
+                Token::Text(text) => {
+                    let content = text.trim_start_matches(|c: char| c.is_ascii_whitespace());
+                    self.insert_text(&text[..text.len() - content.len()]);
+                    if content.is_empty() {
+                        return;
+                    }
+                    token = Token::Text(content.to_string());
+                }
+                _ => {}
+            }
+            let (start, end) = match &token {
+                Token::Start { name, .. } => (name.to_ascii_lowercase(), String::new()),
+                Token::End(name) => (String::new(), name.to_ascii_lowercase()),
+                _ => (String::new(), String::new()),
+            };
+            match (self.mode, start.as_str(), end.as_str()) {
+                (Mode::Initial, "html", _) | (Mode::BeforeHead, "head", _) => {
+                    let next = if start == "html" {
+                        Mode::BeforeHead
+                    } else {
+                        Mode::InHead
+                    };
+                    let Token::Start { attrs, .. } = token else {
+                        unreachable!()
+                    };
+                    self.push(element(&start, attrs, false));
+                    self.mode = next;
+                    return;
+                }
+                (Mode::AfterHead, "body", _) => {
+                    let Token::Start { attrs, .. } = token else {
+                        unreachable!()
+                    };
+                    self.push(element("body", attrs, false));
+                    self.mode = Mode::InBody;
+                    return;
+                }
+                (Mode::InHead, name, _) if HEAD_ELEMENTS.contains(&name) => {
+                    return self.in_body(token, tokenizer)
+                }
+                (Mode::InHead, "head", _) => return,
+                (Mode::InHead, _, "head") => {
+                    self.advance();
+                    return;
+                }
+                // end tags of elements open in `head`, others are ignored
+                (Mode::InHead, "", end) if !end.is_empty() => {
+                    if self.find(end, &["head"]).is_some() {
+                        self.in_body(token, tokenizer);
+                    }
+                    return;
+                }
+                // other end tags before the body are ignored
+                (Mode::Initial | Mode::BeforeHead | Mode::AfterHead, "", end)
+                    if !end.is_empty() =>
+                {
+                    return
+                }
+                _ => self.advance(),
+            }
+        }
+        self.in_body(token, tokenizer);
+    }
+
+    fn in_body(&mut self, token: Token, tokenizer: &mut Tokenizer) {
+        let skip_newline = std::mem::take(&mut self.skip_newline);
+        match token {
+            Token::Doctype(text) => {
+                // only a fragment keeps a doctype, at its top level
+                if self.stack.len() == 1 && !self.document {
+                    self.insert(Doctype(text));
+                }
+            }
+            Token::Comment(text) => self.insert(Comment(text)),
+            Token::Text(text) => {
+                let text = if skip_newline {
+                    text.strip_prefix('\n').unwrap_or(&text)
+                } else {
+                    &text
+                };
+                if !text.is_empty() {
+                    self.reconstruct();
+                    self.insert_text(text);
+                }
+            }
+            Token::Start {
+                name,
+                attrs,
+                self_closing,
+            } => self.start_tag(name, attrs, self_closing, tokenizer),
+            Token::End(name) => self.end_tag(name),
+        }
+    }
+
+    fn start_tag(
+        &mut self,
+        name: String,
+        attrs: Vec<(String, String)>,
+        self_closing: bool,
+        tokenizer: &mut Tokenizer,
+    ) {
+        if self.in_foreign() {
+            self.push(element(&name, attrs, true));
+            if self_closing {
+                self.pop();
+            }
+            return;
+        }
+        let mut name = name.to_ascii_lowercase();
+        if name == "image" {
+            name = "img".to_string();
+        }
+        let name = name.as_str();
+        if self.document && matches!(name, "html" | "body" | "head") {
+            // attributes of a repeated `html` or `body` are added to the first one
+            if let Some(idx) = self.find(name, &[]).filter(|_| name != "head") {
+                let el = &mut self.stack[idx];
+                let extra = element(name, attrs, false);
+                for (key, value) in extra.props.0 {
+                    if !el.props.0.contains_key(&key) {
+                        el.props.0.insert(key, value);
+                    }
+                }
+            }
+            return;
+        }
+        if matches!(name, "svg" | "math") {
+            self.reconstruct();
+            self.push(element(name, attrs, true));
+            if self_closing {
+                self.pop();
+            }
+            return;
+        }
+        if CLOSE_P_ELEMENTS.contains(&name)
+            || matches!(name, "li" | "dd" | "dt" | "listing" | "plaintext" | "xmp")
+        {
+            self.close_named("p", &[SCOPE_BOUNDARIES, &["button"]].concat());
+        }
+        let current = self.current().tag.clone();
+        match name {
+            "li" => self.close_named("li", &[SCOPE_BOUNDARIES, &["ol", "ul", "menu"]].concat()),
+            "dd" | "dt" => {
+                let boundaries = [SCOPE_BOUNDARIES, &["dl"]].concat();
+                self.close_named("dd", &boundaries);
+                self.close_named("dt", &boundaries);
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6"
+                if matches!(&*current, "h1" | "h2" | "h3" | "h4" | "h5" | "h6") =>
+            {
+                self.pop();
+            }
+            "a" => {
+                self.reopen.retain(|el| el.tag != "a");
+                self.close_named("a", SCOPE_BOUNDARIES);
+            }
+            "option" if current == "option" => self.pop(),
+            "optgroup" => {
+                if self.current().tag == "option" {
+                    self.pop();
+                }
+                if self.current().tag == "optgroup" {
+                    self.pop();
+                }
+            }
+            "rp" | "rt" if matches!(&*current, "rp" | "rt") => self.pop(),
+            "caption" | "colgroup" | "col" | "tbody" | "tfoot" | "thead" | "tr" | "td" | "th"
+                if !self.table_start(name) =>
+            {
+                return
+            }
+            _ => {}
+        }
+        if INLINE_ELEMENTS.contains(&name)
+            || FORMATTING_ELEMENTS.contains(&name)
+            || name.contains('-')
+        {
+            self.reconstruct();
+        }
+        let el = element(name, attrs, false);
+        if is_void(name) {
+            return self.insert(el);
+        }
+        self.push(el);
+        if let Some(raw) = RAW_TEXT_ELEMENTS
+            .iter()
+            .chain(RCDATA)
+            .find(|raw| **raw == name)
+        {
+            let mut text = tokenizer.raw_text(raw);
+            if name == "textarea" {
+                text = text.strip_prefix('\n').unwrap_or(text);
+            }
+            if RCDATA.contains(raw) {
+                self.insert_text(&decode(text, false));
+            } else {
+                self.insert_text(text);
+            }
+        } else if name == "plaintext" {
+            let text = tokenizer.rest();
+            tokenizer.pos = tokenizer.src.len();
+            self.insert_text(text);
+        }
+        self.skip_newline = matches!(name, "pre" | "listing");
+    }
+
+    /// fix up the table around a table part, `false` if it is dropped
+    fn table_start(&mut self, name: &str) -> bool {
+        let Some(table) = self.find("table", &["template"]) else {
+            // a fragment may be part of a table, such as a row template
+            return !self.document || self.current().tag == "template";
+        };
+        match name {
+            "col" if self.current().tag == "colgroup" => return true,
+            "tr" => {
+                if let Some(row) = self.find("tr", &["table"]) {
+                    self.close(row);
+                }
+                if !TABLE_SECTIONS.contains(&&*self.current().tag) {
+                    self.close_above(table);
+                    self.push(UnitTag::from(("tbody", Children::default())));
+                }
+            }
+            "td" | "th" => {
+                self.close_named("td", &["tr", "table"]);
+                self.close_named("th", &["tr", "table"]);
+                if self.current().tag != "tr" {
+                    if !TABLE_SECTIONS.contains(&&*self.current().tag) {
+                        self.close_above(table);
+                        self.push(UnitTag::from(("tbody", Children::default())));
+                    }
+                    self.push(UnitTag::from(("tr", Children::default())));
+                }
+            }
+            _ => {
+                self.close_above(table);
+                if name == "col" {
+                    self.push(UnitTag::from(("colgroup", Children::default())));
+                }
+            }
+        }
+        true
+    }
+
+    /// close open elements inside the one at `idx`
+    fn close_above(&mut self, idx: usize) {
+        if self.stack.len() > idx + 1 {
+            self.close(idx + 1);
+        }
+    }
+
+    fn end_tag(&mut self, name: String) {
+        if self.in_foreign() {
+            return self.close_named(&name, &[]);
+        }
+        let name = name.to_ascii_lowercase();
+        match name.as_str() {
+            "html" | "body" | "head" if self.document => {}
+            // `</br>` is read as `<br>`
+            "br" => self.insert(UnitTag::from(("br", Children::default()))),
+            // `</p>` without `<p>` is an empty paragraph
+            "p" if self
+                .find("p", &[SCOPE_BOUNDARIES, &["button"]].concat())
+                .is_none() =>
+            {
+                self.push(UnitTag::from(("p", Children::default())));
+                self.pop();
+            }
+            name => match self.find(name, SCOPE_BOUNDARIES) {
+                Some(idx) => self.close(idx),
+                // end tag of a reopened element stops reopening it
+                None => {
+                    if let Some(idx) = self.reopen.iter().rposition(|el| el.tag == name) {
+                        self.reopen.remove(idx);
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// element named `name` with `attrs` as props
+fn element(name: &str, attrs: Vec<(String, String)>, foreign: bool) -> UnitTag {
+    let tag = if foreign {
+        static_name(name)
+    } else {
+        static_name(&name.to_ascii_lowercase())
+    };
+    let mut el = UnitTag::new(tag);
+    for (attr, value) in attrs {
+        let attr = if foreign {
+            attr
+        } else {
+            attr.to_ascii_lowercase()
+        };
+        if attr == "style" {
+            if let Some(style) = split_style(&value) {
+                el.style = TagStyle(style, vec![]);
+            }
+        } else if let Some(event) = attr
+            .strip_prefix("on")
+            .filter(|e| !e.is_empty() && !foreign)
+        {
+            el.on.0.insert(event, value.as_str());
+        }
+        el.props.0.insert(attr, value);
+    }
+    el
+}
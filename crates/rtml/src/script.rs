@@ -1 +1,274 @@
+use std::fmt::Write;
 
+/// a named js function, handlers call it by name and its definition is written
+/// once into a generated `<script>` in head of the [`crate::Document`].
+/// a tag rendered on its own does not write it, render it with
+/// [`TagFormatter::render_fragment`](crate::tags::TagFormatter::render_fragment) to get the `<script>` after it.
+/// handlers pass `event` to a param named `event` and `undefined` to the others,
+/// which are only given by calls from other scripts
+///
+/// ```
+/// use rtml::{on, script::JsFunction, tags::*, Document};
+///
+/// let greet = JsFunction::new("greet", &["event"], "alert(event.target.value);");
+/// let page = Document::new().body(body(input(on! { change = &greet })));
+/// let html = page.to_string();
+/// assert!(html.contains(r#"onchange="greet(event)""#));
+/// assert!(html.contains("function greet(event) {alert(event.target.value);}"));
+///
+/// let field = input(on! { change = &greet });
+/// assert!(!field.to_string().contains("<script>"));
+/// let mut fragment = String::new();
+/// TagFormatter::compact().render_fragment(&field, &mut fragment).unwrap();
+/// assert_eq!(
+///     fragment,
+///     r#"<input onchange="greet(event)"><script>function greet(event) {alert(event.target.value);}</script>"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsFunction {
+    pub name: String,
+    pub params: Vec<String>,
+    pub body: String,
+}
+
+impl JsFunction {
+    /// # Panics
+    ///
+    /// if `name` or one of `params` is not a js identifier, see [`is_js_identifier`],
+    /// or is a reserved word such as `this`
+    pub fn new<N: Into<String>, B: Into<String>>(name: N, params: &[&str], body: B) -> Self {
+        let name = name.into();
+        for name in params.iter().copied().chain([name.as_str()]) {
+            assert!(
+                is_js_identifier(name) && !JS_RESERVED_WORDS.contains(&name),
+                "invalid js function or param name {:?}",
+                name
+            );
+        }
+        Self {
+            name,
+            params: params.iter().map(|p| p.to_string()).collect(),
+            body: body.into(),
+        }
+    }
+
+    /// js code of calling this function from an inline handler,
+    /// a param named `event` is passed the event, others are `undefined`
+    pub fn call(&self) -> String {
+        let args: Vec<&str> = self
+            .params
+            .iter()
+            .map(|p| match p.as_str() {
+                "event" => "event",
+                _ => "undefined",
+            })
+            .collect();
+        format!("{}({})", self.name, args.join(", "))
+    }
+
+    /// js code of the function declaration
+    pub fn definition(&self) -> String {
+        format!(
+            "function {}({}) {{{}}}",
+            self.name,
+            self.params.join(", "),
+            self.body
+        )
+    }
+}
+
+/// words which can not name a js function or param
+const JS_RESERVED_WORDS: &[&str] = &[
+    "arguments",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "eval",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+/// whether `name` is a js identifier, letters, digits, `_` and `$` not starting with a digit,
+/// which function, param and event names must be
+///
+/// ```
+/// use rtml::script::is_js_identifier;
+///
+/// assert!(is_js_identifier("greet"));
+/// assert!(is_js_identifier("DOMContentLoaded"));
+/// assert!(!is_js_identifier("go()"));
+/// assert!(!is_js_identifier("1st"));
+/// ```
+pub fn is_js_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+/// the `event` object available in inline handlers, used by closure handlers
+/// to build js code without spelling property names by hand
+///
+/// ```
+/// use rtml::{on, tags::*};
+///
+/// let form = form(on! { submit = |e: &rtml::script::JsEvent| e.prevent_default() });
+/// assert!(form.to_string().contains(r#"onsubmit="event.preventDefault()""#));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsEvent;
+
+impl JsEvent {
+    /// `event.target`
+    pub fn target(&self) -> String {
+        "event.target".to_string()
+    }
+
+    /// `event.target.value`, value of input elements
+    pub fn value(&self) -> String {
+        "event.target.value".to_string()
+    }
+
+    /// `event.preventDefault()`
+    pub fn prevent_default(&self) -> String {
+        "event.preventDefault()".to_string()
+    }
+
+    /// `event.stopPropagation()`
+    pub fn stop_propagation(&self) -> String {
+        "event.stopPropagation()".to_string()
+    }
+}
+
+/// js code bound to an event and the function definitions it needs
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Handler {
+    pub code: String,
+    pub functions: Vec<JsFunction>,
+}
+
+/// values accepted as event handler by `on!`
+pub trait IntoHandler {
+    fn into_handler(self) -> Handler;
+}
+
+impl IntoHandler for Handler {
+    fn into_handler(self) -> Handler {
+        self
+    }
+}
+
+/// a js snippet, e.g. `"alert('hi')"`
+impl IntoHandler for &str {
+    fn into_handler(self) -> Handler {
+        Handler {
+            code: self.to_string(),
+            functions: vec![],
+        }
+    }
+}
+
+impl IntoHandler for String {
+    fn into_handler(self) -> Handler {
+        Handler {
+            code: self,
+            functions: vec![],
+        }
+    }
+}
+
+impl IntoHandler for JsFunction {
+    fn into_handler(self) -> Handler {
+        Handler {
+            code: self.call(),
+            functions: vec![self],
+        }
+    }
+}
+
+impl IntoHandler for &JsFunction {
+    fn into_handler(self) -> Handler {
+        self.clone().into_handler()
+    }
+}
+
+/// a closure building js code from the typed [`JsEvent`]
+impl<F: Fn(&JsEvent) -> String> IntoHandler for F {
+    fn into_handler(self) -> Handler {
+        self(&JsEvent).into_handler()
+    }
+}
+
+/// quote `src` as a js string literal, so rust values can be embedded in handler code
+///
+/// ```
+/// let code = format!("alert({})", rtml::script::js_string("it's \"ok\"\n</script>"));
+/// assert_eq!(code, r#"alert("it's \"ok\"\n<\/script>")"#);
+/// ```
+pub fn js_string(src: &str) -> String {
+    let mut buf = String::with_capacity(src.len() + 2);
+    buf.push('"');
+    let mut prev = '\0';
+    for c in src.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            '/' if prev == '<' => buf.push_str("\\/"),
+            '\u{2028}' => buf.push_str("\\u2028"),
+            '\u{2029}' => buf.push_str("\\u2029"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(buf, "\\u{:04x}", c as u32);
+            }
+            c => buf.push(c),
+        }
+        prev = c;
+    }
+    buf.push('"');
+    buf
+}
@@ -1,33 +1,1534 @@
-/// helper macro to create css style
+//! [`style!`](crate::style) checks property names against [`PROPERTIES`] and values
+//! against the kind of the property, lengths, colors, keyword enums or numbers,
+//! every property also takes a string, a [`Global`] keyword or a [`var`] reference
+//!
+//! ```
+//! use rtml::{style, style::*, tags::*};
+//!
+//! let card = div((
+//!     style! {
+//!         display: Display::Flex;
+//!         padding: rem(1.5);
+//!         width: percent(40);
+//!         font-size: px(12);
+//!         color: rgb(51, 51, 51);
+//!         background-color: hex(0xf5f5f5);
+//!         border-color: NamedColor::Silver;
+//!         font-family: "sans-serif";
+//!         --gap: px(4);
+//!         -webkit-user-select: UserSelect::None
+//!     },
+//!     "card",
+//! ));
+//! assert!(card.to_string().contains(
+//!     "display: flex; padding: 1.5rem; width: 40%; font-size: 12px; color: rgb(51, 51, 51); \
+//!      background-color: #f5f5f5; border-color: silver; font-family: sans-serif; \
+//!      --gap: 4px; -webkit-user-select: none;"
+//! ));
+//! ```
+//!
+//! unknown property names and values of the wrong kind are compile errors
+//!
+//! ```compile_fail
+//! let s = rtml::style! { fnot-size: "12px" };
+//! ```
+//!
+//! ```compile_fail
+//! let s = rtml::style! { font-size: 100 };
+//! ```
+
+use std::{
+    fmt::{self, Write},
+    sync::Arc,
+};
+
+use crate::{
+    content::{Category, Text},
+    tags::{OrderedMap, TagFormatter, TagStyle},
+    Tag,
+};
+
+/// helper macro to create css style, declarations are `name: value`, separated by `;`
+///
+/// ```
+/// use rtml::{style, style::*};
 ///
-/// ```no_run
 /// let s = style! {
 ///     background-color: "#fffff";
-///     bar: "bxx";
-///     font-size: 100;
-///     escaped: r#""abc""#
+///     font-size: px(100);
+///     content: r#""abc""#;
 /// };
+/// let mut css = String::new();
+/// s.format(&Default::default(), &mut css).unwrap();
+/// assert_eq!(css, r#"background-color: #fffff; font-size: 100px; content: "abc"; "#);
 /// ```
-/// generated
-/// ```css
-/// background-color: #fffff;
-/// bar: bxx;
-/// font-size: 100;
-/// escaped: "abc";
-/// ```
+///
+/// names must be [known properties](PROPERTIES), custom properties starting with `--`
+/// or vendor prefixed with `-webkit-`, `-moz-`, `-ms-` or `-o-`, values are
+/// [`CssValue`]s of the property, see the [module docs](self)
 #[macro_export]
 macro_rules! style {
-    ($($($name:ident)-+: $value:expr);+) => {
-        {
-            let mut map: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+    ($($tt:tt)*) => {
+        $crate::__private::style_impl!($crate; $($tt)*)
+    };
+}
+
+/// `Self` can be the value of properties whose values are `K`
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a valid value for properties of `{K}`",
+    label = "expect `{K}`, a string, a `Global` keyword or a `var()`"
+)]
+pub trait CssValue<K> {
+    fn to_css(self) -> String;
+}
+
+/// values of custom, vendor prefixed and untyped properties, accepts every value
+pub struct Any;
+
+/// values of unitless number properties, such as `opacity` and `z-index`
+pub struct Number;
+
+/// values of properties taking either a length or a number, such as `line-height`
+pub struct LengthOrNumber;
+
+impl<K> CssValue<K> for &str {
+    fn to_css(self) -> String {
+        self.to_string()
+    }
+}
+
+impl<K> CssValue<K> for String {
+    fn to_css(self) -> String {
+        self
+    }
+}
+
+impl<K> CssValue<K> for &String {
+    fn to_css(self) -> String {
+        self.clone()
+    }
+}
+
+macro_rules! numbers {
+    ($($ty:ty),+) => {
+        $(
+            impl CssValue<Number> for $ty {
+                fn to_css(self) -> String {
+                    self.to_string()
+                }
+            }
+
+            impl CssValue<LengthOrNumber> for $ty {
+                fn to_css(self) -> String {
+                    self.to_string()
+                }
+            }
+
+            impl CssValue<FontWeight> for $ty {
+                fn to_css(self) -> String {
+                    self.to_string()
+                }
+            }
+
+            impl CssValue<Any> for $ty {
+                fn to_css(self) -> String {
+                    self.to_string()
+                }
+            }
+        )+
+    };
+}
+
+numbers!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+/// keywords every property accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Global {
+    Inherit,
+    Initial,
+    Unset,
+    Revert,
+}
+
+impl Global {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Inherit => "inherit",
+            Self::Initial => "initial",
+            Self::Unset => "unset",
+            Self::Revert => "revert",
+        }
+    }
+}
+
+impl fmt::Display for Global {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<K> CssValue<K> for Global {
+    fn to_css(self) -> String {
+        self.as_str().to_string()
+    }
+}
+
+/// reference to a custom property, `var(--name)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Var(pub String);
+
+/// `var(name)`, a custom property name starts with `--`
+///
+/// ```
+/// use rtml::style;
+///
+/// assert_eq!(style::var("--gap").to_string(), "var(--gap)");
+/// ```
+pub fn var<S: Into<String>>(name: S) -> Var {
+    Var(name.into())
+}
+
+impl fmt::Display for Var {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "var({})", self.0)
+    }
+}
+
+impl<K> CssValue<K> for Var {
+    fn to_css(self) -> String {
+        self.to_string()
+    }
+}
+
+/// units of [`Length`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+    Px,
+    Em,
+    Rem,
+    Percent,
+    Vw,
+    Vh,
+    Vmin,
+    Vmax,
+    Ch,
+    Pt,
+    Cm,
+    Mm,
+}
+
+impl Unit {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Px => "px",
+            Self::Em => "em",
+            Self::Rem => "rem",
+            Self::Percent => "%",
+            Self::Vw => "vw",
+            Self::Vh => "vh",
+            Self::Vmin => "vmin",
+            Self::Vmax => "vmax",
+            Self::Ch => "ch",
+            Self::Pt => "pt",
+            Self::Cm => "cm",
+            Self::Mm => "mm",
+        }
+    }
+}
+
+/// a length or percentage, built with [`px`], [`rem`], [`percent`] and friends
+///
+/// ```
+/// use rtml::style::*;
+///
+/// assert_eq!(px(12).to_string(), "12px");
+/// assert_eq!(rem(1.5).to_string(), "1.5rem");
+/// assert_eq!(percent(40).to_string(), "40%");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Length {
+    pub value: f64,
+    pub unit: Unit,
+}
+
+impl Length {
+    pub fn new<V: Into<f64>>(value: V, unit: Unit) -> Self {
+        Self {
+            value: value.into(),
+            unit,
+        }
+    }
+}
+
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.value, self.unit.as_str())
+    }
+}
+
+macro_rules! units {
+    ($($func:ident => $unit:ident),+ $(,)?) => {
+        $(
+            #[doc = concat!("length in `", stringify!($func), "`")]
+            pub fn $func<V: Into<f64>>(value: V) -> Length {
+                Length::new(value, Unit::$unit)
+            }
+        )+
+    };
+}
+
+units! {
+    px => Px,
+    em => Em,
+    rem => Rem,
+    percent => Percent,
+    vw => Vw,
+    vh => Vh,
+    vmin => Vmin,
+    vmax => Vmax,
+    ch => Ch,
+    pt => Pt,
+    cm => Cm,
+    mm => Mm,
+}
+
+impl CssValue<Length> for Length {
+    fn to_css(self) -> String {
+        self.to_string()
+    }
+}
+
+impl CssValue<LengthOrNumber> for Length {
+    fn to_css(self) -> String {
+        self.to_string()
+    }
+}
+
+impl CssValue<VerticalAlign> for Length {
+    fn to_css(self) -> String {
+        self.to_string()
+    }
+}
+
+impl CssValue<Any> for Length {
+    fn to_css(self) -> String {
+        self.to_string()
+    }
+}
+
+/// a css color
+///
+/// ```
+/// use rtml::style::*;
+///
+/// assert_eq!(rgb(255, 136, 0).to_string(), "rgb(255, 136, 0)");
+/// assert_eq!(rgba(0, 0, 0, 0.5).to_string(), "rgba(0, 0, 0, 0.5)");
+/// assert_eq!(hex(0xff8800).to_string(), "#ff8800");
+/// assert_eq!(Color::from(NamedColor::RebeccaPurple).to_string(), "rebeccapurple");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Color {
+    Rgb(u8, u8, u8),
+    Rgba(u8, u8, u8, f32),
+    /// `#rrggbb`, or `#rrggbbaa` for values above `0xffffff`
+    Hex(u32),
+    Named(NamedColor),
+    CurrentColor,
+    Transparent,
+}
+
+/// `rgb(r, g, b)`
+pub fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color::Rgb(r, g, b)
+}
+
+/// `rgba(r, g, b, alpha)`, alpha is between 0 and 1
+pub fn rgba(r: u8, g: u8, b: u8, alpha: f32) -> Color {
+    Color::Rgba(r, g, b, alpha)
+}
+
+/// hex color, `hex(0xff8800)` is `#ff8800`
+pub fn hex(value: u32) -> Color {
+    Color::Hex(value)
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rgb(r, g, b) => write!(f, "rgb({}, {}, {})", r, g, b),
+            Self::Rgba(r, g, b, a) => write!(f, "rgba({}, {}, {}, {})", r, g, b, a),
+            Self::Hex(value) if *value > 0xffffff => write!(f, "#{:08x}", value),
+            Self::Hex(value) => write!(f, "#{:06x}", value),
+            Self::Named(name) => f.write_str(name.as_str()),
+            Self::CurrentColor => f.write_str("currentcolor"),
+            Self::Transparent => f.write_str("transparent"),
+        }
+    }
+}
+
+impl From<NamedColor> for Color {
+    fn from(name: NamedColor) -> Self {
+        Self::Named(name)
+    }
+}
+
+impl CssValue<Color> for Color {
+    fn to_css(self) -> String {
+        self.to_string()
+    }
+}
+
+impl CssValue<Any> for Color {
+    fn to_css(self) -> String {
+        self.to_string()
+    }
+}
+
+impl CssValue<Color> for NamedColor {
+    fn to_css(self) -> String {
+        self.as_str().to_string()
+    }
+}
+
+macro_rules! css_enum {
+    ($(#[$doc:meta])* $name:ident { $($variant:ident = $value:literal),+ $(,)? }) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
             $(
-                let name = vec![$(stringify!($name)),*];
-                map.insert(name.join("-"), $value.to_string());
+                #[doc = concat!("`", $value, "`")]
+                $variant,
             )+
-            $crate::tags::TagStyle(map)
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $value,)+
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl CssValue<$name> for $name {
+            fn to_css(self) -> String {
+                self.as_str().to_string()
+            }
+        }
+
+        impl CssValue<Any> for $name {
+            fn to_css(self) -> String {
+                self.as_str().to_string()
+            }
         }
     };
-    () => {
-        $crate::tags::TagStyle::default()
+}
+
+css_enum!(
+    /// named css colors
+    NamedColor {
+        AliceBlue = "aliceblue",
+        AntiqueWhite = "antiquewhite",
+        Aqua = "aqua",
+        Aquamarine = "aquamarine",
+        Azure = "azure",
+        Beige = "beige",
+        Bisque = "bisque",
+        Black = "black",
+        BlanchedAlmond = "blanchedalmond",
+        Blue = "blue",
+        BlueViolet = "blueviolet",
+        Brown = "brown",
+        BurlyWood = "burlywood",
+        CadetBlue = "cadetblue",
+        Chartreuse = "chartreuse",
+        Chocolate = "chocolate",
+        Coral = "coral",
+        CornflowerBlue = "cornflowerblue",
+        Cornsilk = "cornsilk",
+        Crimson = "crimson",
+        Cyan = "cyan",
+        DarkBlue = "darkblue",
+        DarkCyan = "darkcyan",
+        DarkGoldenRod = "darkgoldenrod",
+        DarkGray = "darkgray",
+        DarkGreen = "darkgreen",
+        DarkGrey = "darkgrey",
+        DarkKhaki = "darkkhaki",
+        DarkMagenta = "darkmagenta",
+        DarkOliveGreen = "darkolivegreen",
+        DarkOrange = "darkorange",
+        DarkOrchid = "darkorchid",
+        DarkRed = "darkred",
+        DarkSalmon = "darksalmon",
+        DarkSeaGreen = "darkseagreen",
+        DarkSlateBlue = "darkslateblue",
+        DarkSlateGray = "darkslategray",
+        DarkSlateGrey = "darkslategrey",
+        DarkTurquoise = "darkturquoise",
+        DarkViolet = "darkviolet",
+        DeepPink = "deeppink",
+        DeepSkyBlue = "deepskyblue",
+        DimGray = "dimgray",
+        DimGrey = "dimgrey",
+        DodgerBlue = "dodgerblue",
+        FireBrick = "firebrick",
+        FloralWhite = "floralwhite",
+        ForestGreen = "forestgreen",
+        Fuchsia = "fuchsia",
+        Gainsboro = "gainsboro",
+        GhostWhite = "ghostwhite",
+        Gold = "gold",
+        GoldenRod = "goldenrod",
+        Gray = "gray",
+        Green = "green",
+        GreenYellow = "greenyellow",
+        Grey = "grey",
+        Honeydew = "honeydew",
+        HotPink = "hotpink",
+        IndianRed = "indianred",
+        Indigo = "indigo",
+        Ivory = "ivory",
+        Khaki = "khaki",
+        Lavender = "lavender",
+        LavenderBlush = "lavenderblush",
+        LawnGreen = "lawngreen",
+        LemonChiffon = "lemonchiffon",
+        LightBlue = "lightblue",
+        LightCoral = "lightcoral",
+        LightCyan = "lightcyan",
+        LightGoldenRodYellow = "lightgoldenrodyellow",
+        LightGray = "lightgray",
+        LightGreen = "lightgreen",
+        LightGrey = "lightgrey",
+        LightPink = "lightpink",
+        LightSalmon = "lightsalmon",
+        LightSeaGreen = "lightseagreen",
+        LightSkyBlue = "lightskyblue",
+        LightSlateGray = "lightslategray",
+        LightSlateGrey = "lightslategrey",
+        LightSteelBlue = "lightsteelblue",
+        LightYellow = "lightyellow",
+        Lime = "lime",
+        LimeGreen = "limegreen",
+        Linen = "linen",
+        Magenta = "magenta",
+        Maroon = "maroon",
+        MediumAquamarine = "mediumaquamarine",
+        MediumBlue = "mediumblue",
+        MediumOrchid = "mediumorchid",
+        MediumPurple = "mediumpurple",
+        MediumSeaGreen = "mediumseagreen",
+        MediumSlateBlue = "mediumslateblue",
+        MediumSpringGreen = "mediumspringgreen",
+        MediumTurquoise = "mediumturquoise",
+        MediumVioletRed = "mediumvioletred",
+        MidnightBlue = "midnightblue",
+        MintCream = "mintcream",
+        MistyRose = "mistyrose",
+        Moccasin = "moccasin",
+        NavajoWhite = "navajowhite",
+        Navy = "navy",
+        OldLace = "oldlace",
+        Olive = "olive",
+        OliveDrab = "olivedrab",
+        Orange = "orange",
+        OrangeRed = "orangered",
+        Orchid = "orchid",
+        PaleGoldenRod = "palegoldenrod",
+        PaleGreen = "palegreen",
+        PaleTurquoise = "paleturquoise",
+        PaleVioletRed = "palevioletred",
+        PapayaWhip = "papayawhip",
+        PeachPuff = "peachpuff",
+        Peru = "peru",
+        Pink = "pink",
+        Plum = "plum",
+        PowderBlue = "powderblue",
+        Purple = "purple",
+        RebeccaPurple = "rebeccapurple",
+        Red = "red",
+        RosyBrown = "rosybrown",
+        RoyalBlue = "royalblue",
+        SaddleBrown = "saddlebrown",
+        Salmon = "salmon",
+        SandyBrown = "sandybrown",
+        SeaGreen = "seagreen",
+        SeaShell = "seashell",
+        Sienna = "sienna",
+        Silver = "silver",
+        SkyBlue = "skyblue",
+        SlateBlue = "slateblue",
+        SlateGray = "slategray",
+        SlateGrey = "slategrey",
+        Snow = "snow",
+        SpringGreen = "springgreen",
+        SteelBlue = "steelblue",
+        Tan = "tan",
+        Teal = "teal",
+        Thistle = "thistle",
+        Tomato = "tomato",
+        Turquoise = "turquoise",
+        Violet = "violet",
+        Wheat = "wheat",
+        White = "white",
+        WhiteSmoke = "whitesmoke",
+        Yellow = "yellow",
+        YellowGreen = "yellowgreen",
+    }
+);
+
+css_enum!(
+    /// `display`
+    Display {
+        None = "none",
+        Block = "block",
+        Inline = "inline",
+        InlineBlock = "inline-block",
+        Flex = "flex",
+        InlineFlex = "inline-flex",
+        Grid = "grid",
+        InlineGrid = "inline-grid",
+        FlowRoot = "flow-root",
+        Contents = "contents",
+        ListItem = "list-item",
+        Table = "table",
+        TableRow = "table-row",
+        TableCell = "table-cell",
+    }
+);
+
+css_enum!(
+    /// `position`
+    Position {
+        Static = "static",
+        Relative = "relative",
+        Absolute = "absolute",
+        Fixed = "fixed",
+        Sticky = "sticky",
+    }
+);
+
+css_enum!(
+    /// `float`
+    Float {
+        None = "none",
+        Left = "left",
+        Right = "right",
+        InlineStart = "inline-start",
+        InlineEnd = "inline-end",
+    }
+);
+
+css_enum!(
+    /// `clear`
+    Clear {
+        None = "none",
+        Left = "left",
+        Right = "right",
+        Both = "both",
+    }
+);
+
+css_enum!(
+    /// `box-sizing`
+    BoxSizing {
+        ContentBox = "content-box",
+        BorderBox = "border-box",
+    }
+);
+
+css_enum!(
+    /// `overflow`, `overflow-x` and `overflow-y`
+    Overflow {
+        Visible = "visible",
+        Hidden = "hidden",
+        Clip = "clip",
+        Scroll = "scroll",
+        Auto = "auto",
+    }
+);
+
+css_enum!(
+    /// `visibility`
+    Visibility {
+        Visible = "visible",
+        Hidden = "hidden",
+        Collapse = "collapse",
+    }
+);
+
+css_enum!(
+    /// `object-fit`
+    ObjectFit {
+        Fill = "fill",
+        Contain = "contain",
+        Cover = "cover",
+        None = "none",
+        ScaleDown = "scale-down",
+    }
+);
+
+css_enum!(
+    /// `vertical-align`, also accepts a [`Length`]
+    VerticalAlign {
+        Baseline = "baseline",
+        Sub = "sub",
+        Super = "super",
+        TextTop = "text-top",
+        TextBottom = "text-bottom",
+        Middle = "middle",
+        Top = "top",
+        Bottom = "bottom",
+    }
+);
+
+css_enum!(
+    /// `border-style` and `outline-style`
+    BorderStyle {
+        None = "none",
+        Hidden = "hidden",
+        Dotted = "dotted",
+        Dashed = "dashed",
+        Solid = "solid",
+        Double = "double",
+        Groove = "groove",
+        Ridge = "ridge",
+        Inset = "inset",
+        Outset = "outset",
+    }
+);
+
+css_enum!(
+    /// `font-weight`, also accepts numbers such as `700`
+    FontWeight {
+        Normal = "normal",
+        Bold = "bold",
+        Bolder = "bolder",
+        Lighter = "lighter",
+    }
+);
+
+css_enum!(
+    /// `font-style`
+    FontStyle {
+        Normal = "normal",
+        Italic = "italic",
+        Oblique = "oblique",
+    }
+);
+
+css_enum!(
+    /// `text-align`
+    TextAlign {
+        Left = "left",
+        Right = "right",
+        Center = "center",
+        Justify = "justify",
+        Start = "start",
+        End = "end",
+    }
+);
+
+css_enum!(
+    /// `text-decoration` and `text-decoration-line`
+    TextDecoration {
+        None = "none",
+        Underline = "underline",
+        Overline = "overline",
+        LineThrough = "line-through",
+    }
+);
+
+css_enum!(
+    /// `text-transform`
+    TextTransform {
+        None = "none",
+        Capitalize = "capitalize",
+        Uppercase = "uppercase",
+        Lowercase = "lowercase",
+    }
+);
+
+css_enum!(
+    /// `text-overflow`
+    TextOverflow {
+        Clip = "clip",
+        Ellipsis = "ellipsis",
+    }
+);
+
+css_enum!(
+    /// `white-space`
+    WhiteSpace {
+        Normal = "normal",
+        Nowrap = "nowrap",
+        Pre = "pre",
+        PreWrap = "pre-wrap",
+        PreLine = "pre-line",
+        BreakSpaces = "break-spaces",
+    }
+);
+
+css_enum!(
+    /// `word-break`
+    WordBreak {
+        Normal = "normal",
+        BreakAll = "break-all",
+        KeepAll = "keep-all",
+        BreakWord = "break-word",
+    }
+);
+
+css_enum!(
+    /// `flex-direction`
+    FlexDirection {
+        Row = "row",
+        RowReverse = "row-reverse",
+        Column = "column",
+        ColumnReverse = "column-reverse",
+    }
+);
+
+css_enum!(
+    /// `flex-wrap`
+    FlexWrap {
+        Nowrap = "nowrap",
+        Wrap = "wrap",
+        WrapReverse = "wrap-reverse",
+    }
+);
+
+css_enum!(
+    /// `justify-content` and `align-content`
+    JustifyContent {
+        Normal = "normal",
+        Start = "start",
+        End = "end",
+        FlexStart = "flex-start",
+        FlexEnd = "flex-end",
+        Center = "center",
+        SpaceBetween = "space-between",
+        SpaceAround = "space-around",
+        SpaceEvenly = "space-evenly",
+        Stretch = "stretch",
+    }
+);
+
+css_enum!(
+    /// `align-items`, `align-self`, `justify-items` and `justify-self`
+    AlignItems {
+        Normal = "normal",
+        Stretch = "stretch",
+        Start = "start",
+        End = "end",
+        FlexStart = "flex-start",
+        FlexEnd = "flex-end",
+        Center = "center",
+        Baseline = "baseline",
+    }
+);
+
+css_enum!(
+    /// `cursor`
+    Cursor {
+        Auto = "auto",
+        Default = "default",
+        None = "none",
+        Pointer = "pointer",
+        Text = "text",
+        Move = "move",
+        Wait = "wait",
+        Progress = "progress",
+        Help = "help",
+        Crosshair = "crosshair",
+        NotAllowed = "not-allowed",
+        Grab = "grab",
+        Grabbing = "grabbing",
+    }
+);
+
+css_enum!(
+    /// `pointer-events`
+    PointerEvents {
+        Auto = "auto",
+        None = "none",
+    }
+);
+
+css_enum!(
+    /// `user-select`
+    UserSelect {
+        Auto = "auto",
+        None = "none",
+        Text = "text",
+        All = "all",
+    }
+);
+
+// `PROPERTIES` and `property`, generated from the table `style!` checks names against
+rtml_macro::css_properties!();
+
+/// `name` is a known property, custom property or vendor prefixed property
+///
+/// ```
+/// use rtml::style::is_valid_property;
+///
+/// assert!(is_valid_property("font-size"));
+/// assert!(is_valid_property("--main-color"));
+/// assert!(is_valid_property("-webkit-line-clamp"));
+/// assert!(!is_valid_property("fnot-size"));
+/// ```
+pub fn is_valid_property(name: &str) -> bool {
+    PROPERTIES.contains(&name)
+        || name.starts_with("--")
+        || ["-webkit-", "-moz-", "-ms-", "-o-"]
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+}
+
+/// a css stylesheet, rendered as css text or embedded with the `style` tag function
+///
+/// nested rules are flattened, `&` stands for the parent selectors and a nested selector
+/// without `&` matches descendants, so the output works in browsers without css nesting
+///
+/// ```
+/// use rtml::{style, style::*, tags::{self, TagFormatter}};
+///
+/// let sheet = Stylesheet::new()
+///     .rule(":root", style! { --accent: hex(0x3366ff) })
+///     .push(
+///         Rule::new(".btn, .link")
+///             .style(style! { color: var("--accent") })
+///             .rule("&:hover", style! { text-decoration: TextDecoration::Underline })
+///             .rule(".icon", style! { width: px(16) }),
+///     )
+///     .media(
+///         "(max-width: 600px)",
+///         Stylesheet::new().rule(".btn", style! { display: Display::Block }),
+///     )
+///     .keyframes(
+///         Keyframes::new("fade")
+///             .frame("from", style! { opacity: 0 })
+///             .frame(percent(100), style! { opacity: 1 }),
+///     );
+/// assert_eq!(
+///     sheet.to_string(),
+///     r#":root {
+///     --accent: #3366ff;
+/// }
+/// .btn, .link {
+///     color: var(--accent);
+/// }
+/// .btn:hover, .link:hover {
+///     text-decoration: underline;
+/// }
+/// .btn .icon, .link .icon {
+///     width: 16px;
+/// }
+/// @media (max-width: 600px) {
+///     .btn {
+///         display: block;
+///     }
+/// }
+/// @keyframes fade {
+///     from {
+///         opacity: 0;
+///     }
+///     100% {
+///         opacity: 1;
+///     }
+/// }
+/// "#
+/// );
+///
+/// let mut out = String::new();
+/// TagFormatter::compact().render(&tags::style(sheet), &mut out).unwrap();
+/// assert!(out.starts_with("<style>:root{--accent:#3366ff;}.btn,.link{color:var(--accent);}"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Stylesheet(pub Vec<CssItem>);
+
+/// an entry of [`Stylesheet`]
+#[derive(Debug, Clone)]
+pub enum CssItem {
+    Rule(Rule),
+    /// `@media query { .. }`
+    Media(String, Stylesheet),
+    /// `@supports condition { .. }`
+    Supports(String, Stylesheet),
+    Keyframes(Keyframes),
+    FontFace(FontFace),
+}
+
+impl From<Rule> for CssItem {
+    fn from(rule: Rule) -> Self {
+        Self::Rule(rule)
+    }
+}
+
+impl From<Keyframes> for CssItem {
+    fn from(keyframes: Keyframes) -> Self {
+        Self::Keyframes(keyframes)
+    }
+}
+
+impl From<FontFace> for CssItem {
+    fn from(font_face: FontFace) -> Self {
+        Self::FontFace(font_face)
+    }
+}
+
+impl Stylesheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// add an item, a [`Rule`] with nested rules, [`Keyframes`] or [`FontFace`]
+    pub fn push<I: Into<CssItem>>(mut self, item: I) -> Self {
+        self.0.push(item.into());
+        self
+    }
+
+    /// add a rule without nested rules
+    pub fn rule<S: Into<String>>(self, selector: S, style: TagStyle) -> Self {
+        self.push(Rule::new(selector).style(style))
+    }
+
+    /// add `@media query { sheet }`
+    pub fn media<S: Into<String>>(mut self, query: S, sheet: Stylesheet) -> Self {
+        self.0.push(CssItem::Media(query.into(), sheet));
+        self
+    }
+
+    /// add `@supports condition { sheet }`
+    pub fn supports<S: Into<String>>(mut self, condition: S, sheet: Stylesheet) -> Self {
+        self.0.push(CssItem::Supports(condition.into(), sheet));
+        self
+    }
+
+    pub fn keyframes(self, keyframes: Keyframes) -> Self {
+        self.push(keyframes)
+    }
+
+    pub fn font_face(self, font_face: FontFace) -> Self {
+        self.push(font_face)
+    }
+
+    /// write css text, pretty printed with the indentation of `f`, or without whitespace
+    /// when `f` collapses whitespace
+    pub fn format(&self, f: &TagFormatter, buf: &mut dyn Write) -> fmt::Result {
+        let mut w = CssWriter {
+            f,
+            buf,
+            depth: 0,
+            first: true,
+        };
+        w.items(&self.0, &[])
+    }
+}
+
+impl fmt::Display for Stylesheet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.format(&TagFormatter::default(), f)
+    }
+}
+
+impl Tag for Stylesheet {
+    fn name(&self) -> &str {
+        "#stylesheet"
+    }
+
+    fn format(&self, f: &mut TagFormatter, buf: &mut dyn Write) -> fmt::Result {
+        let mut css = String::new();
+        Stylesheet::format(self, f, &mut css)?;
+        f.write_text(buf, &css)
+    }
+
+    fn categories(&self) -> &'static [Category] {
+        &[Category::Text]
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
+        Some(self)
+    }
+}
+
+impl Text for Stylesheet {}
+
+/// `selector { declarations }`, followed by nested rules
+#[derive(Debug, Clone, Default)]
+pub struct Rule {
+    pub selector: String,
+    pub style: TagStyle,
+    pub nested: Stylesheet,
+}
+
+impl Rule {
+    pub fn new<S: Into<String>>(selector: S) -> Self {
+        Self {
+            selector: selector.into(),
+            ..Default::default()
+        }
+    }
+
+    /// add declarations, replacing properties already set
+    pub fn style(mut self, style: TagStyle) -> Self {
+        for (name, value) in style.0.iter() {
+            self.style.0.insert(name.as_str(), value.as_str());
+        }
+        self
+    }
+
+    /// add a nested item, selectors of nested rules are relative to this rule
+    pub fn push<I: Into<CssItem>>(mut self, item: I) -> Self {
+        self.nested = self.nested.push(item);
+        self
+    }
+
+    /// add a nested rule without rules nested in it
+    pub fn rule<S: Into<String>>(mut self, selector: S, style: TagStyle) -> Self {
+        self.nested = self.nested.rule(selector, style);
+        self
+    }
+
+    /// add `@media query { sheet }`, selectors in `sheet` are relative to this rule
+    pub fn media<S: Into<String>>(mut self, query: S, sheet: Stylesheet) -> Self {
+        self.nested = self.nested.media(query, sheet);
+        self
+    }
+
+    /// add `@supports condition { sheet }`, selectors in `sheet` are relative to this rule
+    pub fn supports<S: Into<String>>(mut self, condition: S, sheet: Stylesheet) -> Self {
+        self.nested = self.nested.supports(condition, sheet);
+        self
+    }
+}
+
+/// `@keyframes name { .. }`
+#[derive(Debug, Clone)]
+pub struct Keyframes {
+    pub name: String,
+    pub frames: Vec<(String, TagStyle)>,
+}
+
+impl Keyframes {
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            frames: vec![],
+        }
+    }
+
+    /// add a frame, `from`, `to` or a percentage such as `percent(50)`
+    pub fn frame<S: ToString>(mut self, offset: S, style: TagStyle) -> Self {
+        self.frames.push((offset.to_string(), style));
+        self
+    }
+}
+
+/// `@font-face { .. }`, descriptors besides `font-family` and `src`
+/// are added with [`FontFace::style`] or [`FontFace::descriptor`]
+///
+/// ```
+/// use rtml::{style, style::*};
+///
+/// let inter = FontFace::new("Inter", r#"url(/fonts/inter.woff2) format("woff2")"#)
+///     .style(style! { font-weight: 400 })
+///     .descriptor("font-display", "swap");
+/// assert_eq!(
+///     Stylesheet::new().font_face(inter).to_string(),
+///     r#"@font-face {
+///     font-family: "Inter";
+///     src: url(/fonts/inter.woff2) format("woff2");
+///     font-weight: 400;
+///     font-display: swap;
+/// }
+/// "#
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FontFace(pub TagStyle);
+
+impl FontFace {
+    pub fn new<F: AsRef<str>, S: Into<String>>(family: F, src: S) -> Self {
+        Self::default()
+            .descriptor("font-family", css_string(family.as_ref()))
+            .descriptor("src", src)
+    }
+
+    /// add descriptors which are also properties, such as `font-weight`
+    pub fn style(mut self, style: TagStyle) -> Self {
+        for (name, value) in style.0.iter() {
+            self.0 .0.insert(name.as_str(), value.as_str());
+        }
+        self
+    }
+
+    /// add any descriptor, such as `font-display` or `unicode-range`
+    pub fn descriptor<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.0 .0.insert(name, value);
+        self
+    }
+}
+
+/// quote `src` as a css string
+fn css_string(src: &str) -> String {
+    let mut quoted = String::with_capacity(src.len() + 2);
+    quoted.push('"');
+    for c in src.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\a "),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// split a selector list at top level commas
+fn split_selectors(selector: &str) -> Vec<String> {
+    let mut selectors = vec![];
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut start = 0;
+    for (idx, c) in selector.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(' | '[') => depth += 1,
+            (None, ')' | ']') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                selectors.push(selector[start..idx].trim().to_string());
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    selectors.push(selector[start..].trim().to_string());
+    selectors.retain(|s| !s.is_empty());
+    selectors
+}
+
+/// resolve nested `selector` against each of `parents`, `&` outside strings is replaced
+/// by the parent, a selector without `&` becomes a descendant of it
+fn nest_selectors(parents: &[String], selector: &str) -> Vec<String> {
+    let selectors = split_selectors(selector);
+    if parents.is_empty() {
+        return selectors;
+    }
+    let mut nested = vec![];
+    for parent in parents {
+        for selector in selectors.iter() {
+            let mut resolved = String::new();
+            let mut quote = None;
+            let mut found = false;
+            for c in selector.chars() {
+                match (quote, c) {
+                    (Some(q), c) if c == q => quote = None,
+                    (None, '"' | '\'') => quote = Some(c),
+                    (None, '&') => {
+                        resolved.push_str(parent);
+                        found = true;
+                        continue;
+                    }
+                    _ => {}
+                }
+                resolved.push(c);
+            }
+            if !found {
+                resolved = format!("{} {}", parent, selector);
+            }
+            nested.push(resolved);
+        }
+    }
+    nested
+}
+
+/// writes flattened css, one token per line when pretty printed
+struct CssWriter<'a> {
+    f: &'a TagFormatter,
+    buf: &'a mut dyn Write,
+    depth: usize,
+    first: bool,
+}
+
+impl CssWriter<'_> {
+    fn token(&mut self, token: &str) -> fmt::Result {
+        if self.f.is_pretty() {
+            let pad = (self.f.indent + self.depth) * self.f.tab_size;
+            write!(self.buf, "{:pad$}{}{}", "", token, self.f.line_sep)?;
+        } else {
+            if !self.first && !self.f.collapse_whitespace {
+                self.buf.write_char(' ')?;
+            }
+            self.buf.write_str(token)?;
+        }
+        self.first = false;
+        Ok(())
+    }
+
+    fn open(&mut self, prelude: &str) -> fmt::Result {
+        if self.f.collapse_whitespace {
+            self.token(&format!("{}{{", prelude))?;
+        } else {
+            self.token(&format!("{} {{", prelude))?;
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn close(&mut self) -> fmt::Result {
+        self.depth -= 1;
+        self.token("}")
+    }
+
+    fn block(&mut self, prelude: &str, style: &TagStyle) -> fmt::Result {
+        self.open(prelude)?;
+        for (name, value) in style.0.entries(self.f.sort_attrs) {
+            if self.f.collapse_whitespace {
+                self.token(&format!("{}:{};", name, value))?;
+            } else {
+                self.token(&format!("{}: {};", name, value))?;
+            }
+        }
+        self.close()
+    }
+
+    fn items(&mut self, items: &[CssItem], parents: &[String]) -> fmt::Result {
+        for item in items {
+            match item {
+                CssItem::Rule(rule) => {
+                    let selectors = nest_selectors(parents, &rule.selector);
+                    if !rule.style.0.is_empty() {
+                        let sep = if self.f.collapse_whitespace {
+                            ","
+                        } else {
+                            ", "
+                        };
+                        self.block(&selectors.join(sep), &rule.style)?;
+                    }
+                    self.items(&rule.nested.0, &selectors)?;
+                }
+                CssItem::Media(query, sheet) => {
+                    self.open(&format!("@media {}", query))?;
+                    self.items(&sheet.0, parents)?;
+                    self.close()?;
+                }
+                CssItem::Supports(condition, sheet) => {
+                    self.open(&format!("@supports {}", condition))?;
+                    self.items(&sheet.0, parents)?;
+                    self.close()?;
+                }
+                CssItem::Keyframes(keyframes) => {
+                    self.open(&format!("@keyframes {}", keyframes.name))?;
+                    for (offset, style) in keyframes.frames.iter() {
+                        self.block(offset, style)?;
+                    }
+                    self.close()?;
+                }
+                CssItem::FontFace(font_face) => self.block("@font-face", &font_face.0)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// a stylesheet whose classes are renamed with a hash of its content, like css modules,
+/// so components can use short class names without colliding with each other
+///
+/// tags take a generated class with `scoped`, and the stylesheet is written into one
+/// `<style>` in head of the [`Document`](crate::Document), only if such a tag is rendered.
+/// a tag rendered on its own does not write it, render it with
+/// [`TagFormatter::render_fragment`] to get the `<style>` after it
+///
+/// ```
+/// use std::sync::LazyLock;
+///
+/// use rtml::{style, style::*, tags::*, Document};
+///
+/// static CARD: LazyLock<ScopedStyle> = LazyLock::new(|| {
+///     ScopedStyle::new(
+///         Stylesheet::new()
+///             .rule(".card", style! { padding: rem(1) })
+///             .rule(".card:hover .title", style! { color: NamedColor::Teal }),
+///     )
+/// });
+///
+/// fn card(title: &str) -> Result<Div, UnknownClass> {
+///     Ok(div(h2(title.to_string()).scoped(&CARD, "title")?).scoped(&CARD, "card")?)
+/// }
+///
+/// let card_class = CARD.class("card").unwrap();
+/// assert!(card_class.starts_with("card-") && card_class.len() == "card-".len() + 8);
+/// assert_eq!(CARD.class("tilte"), None);
+/// assert!(div(()).scoped(&CARD, "tilte").is_err());
+///
+/// let page = Document::new().body(body((card("a").unwrap(), card("b").unwrap())));
+/// let html = page.to_string();
+/// let css = format!(
+///     "<style>.{}{{padding:1rem;}}.{}:hover .{}{{color:teal;}}</style>",
+///     card_class,
+///     card_class,
+///     CARD.class("title").unwrap(),
+/// );
+/// assert_eq!(html.matches(&css).count(), 1);
+/// assert!(html.contains(&format!(r#"<div class="{}">"#, card_class)));
+///
+/// // nothing is collected for components not rendered
+/// let empty = Document::new().body(body(p("no cards")));
+/// assert!(!empty.to_string().contains("<style>"));
+///
+/// // outside a document the stylesheet is only written by `render_fragment`
+/// assert!(!card("a").unwrap().to_string().contains("<style>"));
+/// let mut fragment = String::new();
+/// TagFormatter::compact().render_fragment(&card("a").unwrap(), &mut fragment).unwrap();
+/// assert!(fragment.ends_with(&css));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScopedStyle {
+    hash: String,
+    classes: OrderedMap,
+    sheet: Arc<Stylesheet>,
+}
+
+impl ScopedStyle {
+    /// rename every class in selectors of `sheet`, `@media` and `@supports` included,
+    /// to `class-hash`, the hash is computed from the css of `sheet`
+    pub fn new(mut sheet: Stylesheet) -> Self {
+        let mut css = String::new();
+        sheet
+            .format(&TagFormatter::compact(), &mut css)
+            .expect("write css to string");
+        let hash = format!("{:08x}", fnv1a(&css));
+        let mut classes = OrderedMap::new();
+        scope_items(&mut sheet.0, &hash, &mut classes);
+        Self {
+            hash,
+            classes,
+            sheet: Arc::new(sheet),
+        }
+    }
+
+    /// hash of the stylesheet, suffix of generated class names
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// generated name of `class`, `None` if the stylesheet does not use it, which is most likely a typo
+    pub fn class(&self, class: &str) -> Option<&str> {
+        self.classes.get(class).map(String::as_str)
+    }
+
+    /// original class names to generated ones
+    pub fn classes(&self) -> &OrderedMap {
+        &self.classes
+    }
+
+    /// the stylesheet with generated class names
+    pub fn sheet(&self) -> &Arc<Stylesheet> {
+        &self.sheet
+    }
+}
+
+/// compact css of scoped stylesheets collected while rendering, each distinct one written once
+pub(crate) fn scoped_css(sheets: &[Arc<Stylesheet>]) -> Result<String, fmt::Error> {
+    let mut css: Vec<String> = vec![];
+    for sheet in sheets {
+        let mut text = String::new();
+        Stylesheet::format(sheet, &TagFormatter::compact(), &mut text)?;
+        if !css.contains(&text) {
+            css.push(text);
+        }
+    }
+    Ok(css.concat())
+}
+
+/// class passed to `scoped` which the [`ScopedStyle`] does not use
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownClass(pub String);
+
+impl fmt::Display for UnknownClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "class {} is not used by the scoped stylesheet", self.0)
+    }
+}
+
+impl std::error::Error for UnknownClass {}
+
+/// 32 bit fnv-1a hash, stable across builds so generated class names can be cached
+fn fnv1a(src: &str) -> u32 {
+    src.bytes().fold(0x811c9dc5, |hash, b| {
+        (hash ^ b as u32).wrapping_mul(0x01000193)
+    })
+}
+
+fn scope_items(items: &mut [CssItem], hash: &str, classes: &mut OrderedMap) {
+    for item in items {
+        match item {
+            CssItem::Rule(rule) => {
+                rule.selector = scope_selector(&rule.selector, hash, classes);
+                scope_items(&mut rule.nested.0, hash, classes);
+            }
+            CssItem::Media(_, sheet) | CssItem::Supports(_, sheet) => {
+                scope_items(&mut sheet.0, hash, classes)
+            }
+            CssItem::Keyframes(_) | CssItem::FontFace(_) => {}
+        }
+    }
+}
+
+/// rename classes in `selector`, outside strings and attribute selectors
+fn scope_selector(selector: &str, hash: &str, classes: &mut OrderedMap) -> String {
+    let is_name = |c: char| c.is_alphanumeric() || c == '-' || c == '_';
+    let mut scoped = String::with_capacity(selector.len());
+    let mut quote = None;
+    let mut in_attr = false;
+    let mut chars = selector.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        scoped.push(c);
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[') => in_attr = true,
+            (None, ']') => in_attr = false,
+            (None, '.') if !in_attr => {
+                let start = idx + 1;
+                let mut end = start;
+                while let Some((idx, c)) = chars.peek().copied() {
+                    if !is_name(c) {
+                        break;
+                    }
+                    end = idx + c.len_utf8();
+                    chars.next();
+                }
+                let class = &selector[start..end];
+                if class.is_empty() {
+                    continue;
+                }
+                let name = format!("{}-{}", class, hash);
+                scoped.push_str(&name);
+                if !classes.contains_key(class) {
+                    classes.insert(class, name);
+                }
+            }
+            _ => {}
+        }
     }
+    scoped
 }
@@ -1,73 +1,360 @@
 use std::{
-    collections::HashMap,
+    borrow::Cow,
     fmt::Write,
     fmt::{Debug, Display},
+    sync::Arc,
 };
 
-use crate::{Children, InnerChildren, Tag};
+pub use crate::attrs::GlobalAttrs;
+use crate::format::{
+    can_omit_end_tag, is_inline_level, static_name, wrap_inline, Budget, LeadingNewline, INLINE_ELEMENTS,
+    PRESERVE_WHITESPACE_ELEMENTS,
+};
+pub use crate::format::{is_void, TagFormatter, VOID_ELEMENTS};
+use crate::{
+    attrs::{is_valid_attr_name, AttrValue},
+    content::Category,
+    escape::{TextContext, RAW_TEXT_ELEMENTS},
+    parse::split_style,
+    script::{is_js_identifier, Handler, IntoHandler, JsFunction},
+    style::{ScopedStyle, Stylesheet, UnknownClass},
+    Children, InnerChildren, Tag,
+};
 
+/// build [`TagProp`], attribute names are joined with `-`, attributes without value are written bare
+///
+/// ```
+/// use rtml::{prop, tags::*};
+///
+/// let input = input(prop! { r#type = "checkbox", aria-label = "agree", checked });
+/// assert_eq!(input.to_string().trim_end(), r#"<input type="checkbox" aria-label="agree" checked>"#);
+/// ```
+///
+/// values are [`AttrValue`](crate::attrs::AttrValue)s, a `bool` makes the attribute present or absent,
+/// and `None` leaves it out
+///
+/// ```
+/// use rtml::{prop, tags::*};
+///
+/// let enabled = false;
+/// let tip: Option<&str> = None;
+/// let btn = button((prop! { disabled = !enabled, title = tip, tabindex = Some(2) }, "ok"));
+/// assert_eq!(btn.to_string().trim_end(), r#"<button disabled tabindex="2">ok</button>"#);
+/// ```
+///
+/// names are checked at compile time, event handlers belong to [`on!`](crate::on),
+/// style to [`style!`](crate::style), and each attribute can only be set once
+///
+/// ```compile_fail
+/// let props = rtml::prop! { onclick = "alert(1)" };
+/// ```
+///
+/// ```compile_fail
+/// let props = rtml::prop! { style = "color: red" };
+/// ```
+///
+/// ```compile_fail
+/// let props = rtml::prop! { id = "a", class = "b", id = "c" };
+/// ```
+///
+/// ```compile_fail
+/// let props = rtml::prop! { "x>y" = 1 };
+/// ```
 #[macro_export]
 macro_rules! prop {
-    ($($($name:tt)-+ $(= $value:expr)?),+) => {{ let mut props: std::collections::HashMap<String, String> = std::collections::HashMap::new();
-        $(
-            let name = vec![$(stringify!($name)),*];
-            let key = name.join("-");
-            if key.starts_with("on") {
-                panic!("event handler {} should be registered with env! macro", key);
-            } else if key == "style" {
-                panic!("style property should be set with style! macro");
-            }
-            let mut no_value = true;
-            $(
-                no_value = false;
-                props.insert(key.to_string(), $value.to_string());
-            )?
-            if no_value {
-                props.insert(key.to_string(), String::new());
-            }
+    ($($tt:tt)*) => {
+        $crate::__private::prop_impl!($crate; $($tt)*)
+    };
+}
 
-        )*
-        $crate::tags::TagProp(props)
+/// bind event handlers, each handler is anything implementing [`IntoHandler`](crate::script::IntoHandler):
+/// a js snippet, a [`JsFunction`](crate::script::JsFunction) or a closure on [`JsEvent`](crate::script::JsEvent)
+///
+/// ```
+/// use rtml::{on, script::JsEvent, tags::*};
+///
+/// let msg = "it's done";
+/// let btn = button((
+///     on! {
+///         click = format!("alert({})", rtml::script::js_string(msg)),
+///         mouseover = |e: &JsEvent| format!("{}.focus()", e.target())
+///     },
+///     "done",
+/// ));
+/// let html = btn.to_string();
+/// assert!(html.contains(r#"onclick="alert(&quot;it's done&quot;)""#));
+/// assert!(html.contains(r#"onmouseover="event.target.focus()""#));
+/// ```
+#[macro_export]
+macro_rules! on {
+    ($($name:ident = $cb:expr),+ $(,)?) => {{
+        let mut handlers = $crate::tags::TagHandler::default();
+        $(
+            handlers.insert(stringify!($name), $cb);
+        )+
+        handlers
     }};
     () => {{
-        $crate::tags::TagProp::default()
+        $crate::tags::TagHandler::default()
     }}
 }
 
-/// and event listener
+/// build tags with html like syntax, expands into the tag functions,
+/// a single root element gives its tag, e.g. [`Div`], several nodes give [`Children`]
+///
+/// - attribute values are literals or `{expression}`, `style={..}` takes a [`TagStyle`]
+///   and `onxxx={..}` anything accepted by [`on!`](crate::on)
+/// - text is quoted, `{expression}` inserts anything converting into [`Children`]
+/// - `for`, `if`, `if let` and `match` work on nodes, their bodies are `{ nodes... }`
+/// - void elements can be written as `<br>` or `<br />`
+/// - children of built in tags are checked against their [content model](crate::content)
+///
+/// ```
+/// use rtml::{html, style};
+///
+/// let items = ["apple", "banana"];
+/// let logged_in = true;
+/// let page = html! {
+///     <div class="list" style={style! { color: "red" }}>
+///         <h1>"fruits"</h1>
+///         <ul>
+///             for item in items.iter() {
+///                 <li data-name={item}>{item.to_string()}</li>
+///             }
+///         </ul>
+///         if logged_in {
+///             <button onclick="logout()">"logout"</button>
+///         } else {
+///             <a href="/login">"login"</a>
+///         }
+///         <br>
+///     </div>
+/// };
+/// let html = page.to_string();
+/// assert!(html.contains(r#"<li data-name="banana">banana</li>"#));
+/// assert!(html.contains(r#"<button onclick="logout()">logout</button>"#));
+/// ```
+///
+/// ```compile_fail
+/// let page = rtml::html! { <div><p>"text"</div> };
+/// ```
 #[macro_export]
-macro_rules! on {
-    ($($name:ident = $cb:ident),+) => {
-      {
-          let mut handlers: std::collections::HashMap<String, String> = std::collections::HashMap::new();
-          $(
-              let key = stringify!($name);
-              let val = stringify!($cb);
-              handlers.insert(key.to_string(), val.to_string());
-
-          )*
-          $crate::tags::TagHandler(props)
-      }
+macro_rules! html {
+    ($($tt:tt)*) => {
+        $crate::__private::html_impl!($crate; $($tt)*)
     };
-    () => {{
-        $crate::tags::TagHandler::default()
-    }}
 }
 
+/// string map which keeps insertion order, so rendered attributes are stable
+///
+/// inserting an existing key replaces its value but keeps its position
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OrderedMap(Vec<(String, String)>);
+
+impl OrderedMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// insert a key value pair, return the old value if key exists
+    pub fn insert<K: Into<String>, V: Into<String>>(&mut self, key: K, value: V) -> Option<String> {
+        let key = key.into();
+        let value = value.into();
+        match self.0.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => Some(std::mem::replace(v, value)),
+            None => {
+                self.0.push((key, value));
+                None
+            }
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&String> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut String> {
+        self.0.iter_mut().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// remove a key, keep order of the rest entries
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        let idx = self.0.iter().position(|(k, _)| k == key)?;
+        Some(self.0.remove(idx).1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// iterate entries in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.0.iter().map(|(k, v)| (k, v))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.0.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &String> {
+        self.0.iter().map(|(_, v)| v)
+    }
+
+    /// entries in insertion order, or sorted by key when `sort` is true
+    pub fn entries(&self, sort: bool) -> Vec<(&String, &String)> {
+        let mut entries: Vec<_> = self.iter().collect();
+        if sort {
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+        }
+        entries
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for OrderedMap {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> Extend<(K, V)> for OrderedMap {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl IntoIterator for OrderedMap {
+    type Item = (String, String);
+    type IntoIter = std::vec::IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// categories and content model check of built in elements, for [`UnitTag`]s built by [`x`]
+macro_rules! builtin {
+    ($($struct:ident),+ $(,)?) => {
+        fn builtin(name: &str) -> Option<(&'static [Category], fn(&dyn Tag) -> bool)> {
+            $(
+                if name == $struct::NAME {
+                    return Some(($struct::CATEGORIES, $struct::PERMITS));
+                }
+            )+
+            None
+        }
+
+        /// whether `name` is a built in element, which has a tag function in this module
+        pub fn is_builtin(name: &str) -> bool {
+            builtin_name(name).is_some()
+        }
+
+        /// the static name of the built in element named `name`
+        pub(crate) fn builtin_name(name: &str) -> Option<&'static str> {
+            $(
+                if name == $struct::NAME {
+                    return Some($struct::NAME);
+                }
+            )+
+            None
+        }
+    };
+}
+
+builtin!(
+    A, Abbr, Address, Area, Article, Aside, Audio, B, Base, Bdi, Bdo, Blockquote, Body, Br, Button,
+    Canvas, Caption, Cite, Code, Col, Colgroup, Content, Data, Datalist, Dd, Del, Details, Dfn,
+    Dialog, Div, Dl, Dt, Em, Embed, Fieldset, Figcaption, Figure, Footer, Form, H1, H2, H3, H4, H5,
+    H6, Head, Header, Hr, Html, I, Iframe, Img, Input, Ins, Kbd, Label, Legend, Li, Link, Main,
+    Map, Mark, Menu, Meta, Meter, Nav, Noscript, Object, Ol, Optgroup, Option_, Output, P, Picture,
+    Portal, Pre, Progress, Q, Rp, Rt, Ruby, S, Samp, Script, Section, Select, Shadow, Slot, Small,
+    Source, Span, Strong, Style, Sub, Summary, Sup, Table, Tbody, Td, Template, Textarea, Tfoot,
+    Th, Thead, Time, Title, Tr, Track, U, Ul, Var, Video, Wbr,
+);
+
 /// simple wrapper of tag props
 #[derive(Debug, Clone, Default)]
-pub struct TagProp(pub HashMap<String, String>);
+pub struct TagProp(pub OrderedMap);
+
+impl TagProp {
+    /// props from name and value pairs, `None` values are left out
+    pub fn from_values<I: IntoIterator<Item = (&'static str, Option<String>)>>(values: I) -> Self {
+        let mut props = Self::default();
+        for (name, value) in values {
+            props.set(name, value);
+        }
+        props
+    }
 
-/// simple wrapper of tag style
+    /// set attribute `name`, remove it if `value` is `None`
+    pub fn set(&mut self, name: &str, value: Option<String>) {
+        match value {
+            Some(value) => {
+                self.0.insert(name, value);
+            }
+            None => {
+                self.0.remove(name);
+            }
+        }
+    }
+}
+
+/// simple wrapper of tag style, declarations and the scoped stylesheets
+/// whose classes the tag uses, see [`ScopedStyle`]
 #[derive(Debug, Clone, Default)]
-pub struct TagStyle(pub HashMap<String, String>);
+pub struct TagStyle(pub OrderedMap, pub Vec<Arc<Stylesheet>>);
 
-/// simple wrapper of tag event handler
+impl TagStyle {
+    /// write declarations as css text, without escaping
+    pub fn format(&self, f: &TagFormatter, buf: &mut dyn Write) -> std::fmt::Result {
+        for (name, val) in self.0.entries(f.sort_attrs) {
+            if f.collapse_whitespace {
+                write!(buf, "{}:{};", name, val)?;
+            } else {
+                write!(buf, "{}: {}; ", name, val)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// simple wrapper of tag event handler, event name without `on` prefix to js code,
+/// and the js functions the code calls
 #[derive(Debug, Clone, Default)]
-pub struct TagHandler(pub HashMap<String, String>);
+pub struct TagHandler(pub OrderedMap, pub Vec<JsFunction>);
+
+impl TagHandler {
+    /// bind `handler` to `event`, e.g. `click`, replace the old one if any
+    ///
+    /// # Panics
+    ///
+    /// if `event` is not a js identifier, see [`is_js_identifier`]
+    pub fn insert<H: IntoHandler>(&mut self, event: &str, handler: H) {
+        assert!(is_js_identifier(event), "invalid event name {:?}", event);
+        let Handler { code, functions } = handler.into_handler();
+        self.0.insert(event, code);
+        for func in functions {
+            if !self.1.contains(&func) {
+                self.1.push(func);
+            }
+        }
+    }
+}
 
 pub struct UnitTag {
-    pub tag: &'static str,
+    /// tag name, borrowed for built in elements
+    pub tag: Cow<'static, str>,
     pub props: TagProp,
     pub on: TagHandler,
     pub style: TagStyle,
@@ -75,86 +362,271 @@ pub struct UnitTag {
 }
 
 impl Tag for UnitTag {
-    fn name(&self) -> &'static str {
-        self.tag
+    fn name(&self) -> &str {
+        &self.tag
     }
 
-    fn format(&self, f: &mut TagFormatter, buf: &mut String) -> std::fmt::Result {
-        let pad = f.pad_size();
-        write!(buf, "{:pad$}<{}", "", self.tag)?;
-        if f.newline_on_prop {
-            buf.push_str(f.line_sep);
-            let pad = pad + 1;
-            write!(buf, "{:pad$}", "")?;
-            for (name, val) in self.props.0.iter() {
-                if val.is_empty() {
-                    write!(buf, r#"{:pad$}{}"#, "", name)?;
-                } else {
-                    write!(buf, r#"{:pad$}{}="{}""#, "", name, val)?;
-                }
-                buf.push_str(f.line_sep);
-            }
-            if !self.style.0.is_empty() {
-                write!(buf, "{:pad$}", "")?;
-                write!(buf, "style=\"")?;
-                for (name, val) in self.style.0.iter() {
-                    write!(buf, "{}: {}; ", name, val)?;
-                }
-                write!(buf, "\"")?;
-            }
-            for (name, val) in self.on.0.iter() {
-                write!(buf, "{:pad$}", "")?;
-                write!(buf, r#"on{}="{}""#, name, val)?;
-                buf.push_str(f.line_sep);
+    fn as_element(&self) -> Option<&UnitTag> {
+        Some(self)
+    }
+
+    fn as_element_mut(&mut self) -> Option<&mut UnitTag> {
+        Some(self)
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
+        Some(self)
+    }
+
+    /// categories of the built in element with the same name, unchecked for other names
+    fn categories(&self) -> &'static [Category] {
+        builtin(&self.tag).map_or(Category::UNCHECKED, |(categories, _)| categories)
+    }
+
+    fn permits(&self, child: &dyn Tag) -> bool {
+        builtin(&self.tag).is_none_or(|(_, permits)| permits(child))
+    }
+
+    fn format(&self, f: &mut TagFormatter, buf: &mut dyn Write) -> std::fmt::Result {
+        // keep short elements on one line, if they fit in `max_width`
+        if f.is_pretty() && (self.has_inline_children(f) || self.children.len() == 1) {
+            let mut line = String::new();
+            let saved = f.clone();
+            f.inline = true;
+            let budget = f.max_width.saturating_sub(f.pad_size());
+            let fits = self.format(f, &mut Budget::new(&mut line, budget)).is_ok();
+            // head entries, scripts and styles collected while rendering the line are kept if it is used
+            let head = std::mem::take(&mut f.head);
+            let scripts = std::mem::take(&mut f.scripts);
+            let styles = std::mem::take(&mut f.styles);
+            *f = saved;
+            if fits {
+                f.head = head;
+                f.scripts = scripts;
+                f.styles = styles;
+                f.begin_line(buf)?;
+                buf.write_str(&line)?;
+                return f.end_line(buf);
             }
-            let pad = pad - 1;
-            write!(buf, "{:pad$}>", "")?;
-            buf.push_str(f.line_sep);
+        }
+        let parent = f.parent.clone();
+        let next = f.next_sibling.clone();
+        for func in self.on.1.iter() {
+            f.push_script(func.definition());
+        }
+        for sheet in self.style.1.iter() {
+            f.push_style(sheet);
+        }
+        f.begin_line(buf)?;
+        write!(buf, "<{}", self.tag)?;
+        self.format_attrs(f, buf)?;
+        buf.write_char('>')?;
+        // void elements have no end tag, children (only possible with a hand built `UnitTag`)
+        // are dropped, the validator reports them as invalid
+        if is_void(&self.tag) {
+            return f.end_line(buf);
+        }
+        let ctx = f.context;
+        let preserve = f.preserve_whitespace;
+        let inline = f.inline;
+        // adding newlines and indentation around inline content changes what browser shows,
+        // so it is written as is, only block level children are put on their own lines
+        let children_inline = self.has_inline_children(f);
+        // but a long inline run in a block element can still be wrapped at its whitespace
+        let wrap = f.is_pretty()
+            && children_inline
+            && !INLINE_ELEMENTS.contains(&&*self.tag)
+            && !RAW_TEXT_ELEMENTS.contains(&&*self.tag)
+            && !PRESERVE_WHITESPACE_ELEMENTS.contains(&&*self.tag);
+        if let Some(raw) = RAW_TEXT_ELEMENTS.iter().find(|raw| **raw == self.tag) {
+            f.context = TextContext::RawText(raw);
+        }
+        if PRESERVE_WHITESPACE_ELEMENTS.contains(&&*self.tag) {
+            f.preserve_whitespace = true;
+        }
+        f.inline = children_inline;
+        if !f.inline || wrap {
+            buf.write_str(f.line_sep)?;
+        }
+        f.indent += 1;
+        if wrap {
+            let mut content = String::new();
+            self.format_children(f, &mut content)?;
+            f.inline = false;
+            wrap_inline(f, &content, buf)?;
+        } else if f.preserve_whitespace && !preserve {
+            // browsers drop a newline right after `<pre>` or `<textarea>`, write an extra one to keep it
+            self.format_children(f, &mut LeadingNewline::new(buf))?;
         } else {
-            for (name, val) in self.props.0.iter() {
-                if val.is_empty() {
-                    write!(buf, r#" {}"#, name)?;
-                } else {
-                    write!(buf, r#" {}="{}""#, name, val)?;
-                }
+            self.format_children(f, buf)?;
+        }
+        f.indent -= 1;
+        if !f.inline {
+            f.begin_line(buf)?;
+        }
+        f.context = ctx;
+        f.preserve_whitespace = preserve;
+        f.inline = inline;
+        if f.omit_optional_end_tags && can_omit_end_tag(&self.tag, next.as_deref(), &parent) {
+            return f.end_line(buf);
+        }
+        write!(buf, "</{}>", self.tag)?;
+        f.end_line(buf)
+    }
+}
+
+impl UnitTag {
+    /// whether children are written in inline formatting context
+    fn has_inline_children(&self, f: &TagFormatter) -> bool {
+        f.inline
+            || f.preserve_whitespace
+            || f.context != TextContext::Text
+            || RAW_TEXT_ELEMENTS.contains(&&*self.tag)
+            || PRESERVE_WHITESPACE_ELEMENTS.contains(&&*self.tag)
+            || INLINE_ELEMENTS.contains(&&*self.tag)
+            // nothing in head is displayed, its raw entries can always be laid out as blocks
+            || (self.tag != "head" && self.children.iter().any(|c| is_inline_level(c.name())))
+    }
+
+    fn has_attrs(&self) -> bool {
+        !(self.props.0.is_empty() && self.style.0.is_empty() && self.on.0.is_empty())
+    }
+
+    fn format_children(&self, f: &mut TagFormatter, buf: &mut dyn Write) -> std::fmt::Result {
+        for (idx, child) in self.children.iter().enumerate() {
+            f.parent = self.tag.clone();
+            f.next_sibling = self.children.get(idx + 1).map(|c| static_name(c.name()));
+            child.format(f, buf)?
+        }
+        Ok(())
+    }
+
+    /// write props, style and handlers of start tag, each on its own line
+    /// if `newline_on_prop` is set or start tag does not fit in `max_width`
+    fn format_attrs(&self, f: &TagFormatter, buf: &mut dyn Write) -> std::fmt::Result {
+        if !self.has_attrs() {
+            return Ok(());
+        }
+        let mut newline = f.newline_on_prop;
+        if !newline && f.is_pretty() {
+            let mut line = String::new();
+            // `<`, tag name and `>`
+            let budget = f
+                .max_width
+                .saturating_sub(f.pad_size() + self.tag.len() + 2);
+            if self
+                .write_attrs(f, false, &mut Budget::new(&mut line, budget))
+                .is_ok()
+            {
+                return buf.write_str(&line);
             }
-            if !self.style.0.is_empty() {
-                write!(buf, " style=\"")?;
-                for (name, val) in self.style.0.iter() {
-                    write!(buf, "{}: {}; ", name, val)?;
-                }
-                write!(buf, "\"")?;
+            newline = true;
+        }
+        self.write_attrs(f, newline, buf)?;
+        if newline {
+            let pad = f.pad_size();
+            write!(buf, "{}{:pad$}", f.line_sep, "")?;
+        }
+        Ok(())
+    }
+
+    fn write_attrs(
+        &self,
+        f: &TagFormatter,
+        newline: bool,
+        buf: &mut dyn Write,
+    ) -> std::fmt::Result {
+        let pad = f.pad_size() + f.tab_size;
+        let sep = |buf: &mut dyn Write| {
+            if newline {
+                write!(buf, "{}{:pad$}", f.line_sep, "")
+            } else {
+                buf.write_char(' ')
             }
-            for (name, val) in self.on.0.iter() {
-                write!(buf, r#" on{}="{}""#, name, val)?;
+        };
+        // a `style` or `on*` prop, such as one kept by the parser, is written once in its place,
+        // with the value of style or handlers if they set the same attribute
+        let mut style_prop = false;
+        for (name, val) in self.props.0.entries(f.sort_attrs) {
+            sep(buf)?;
+            if name.eq_ignore_ascii_case("style") && !self.style.0.is_empty() {
+                style_prop = true;
+                f.write_attr(buf, name, &self.merged_style(f, val)?)?;
+            } else if let Some(code) = event_name(name).and_then(|event| self.handler(event)) {
+                f.write_attr(buf, name, code)?;
+            } else {
+                f.write_attr(buf, name, val)?;
             }
-            buf.push('>');
         }
-        buf.push_str(f.line_sep);
-        f.indent += 1;
-        for child in self.children.iter() {
-            child.format(f, buf)?
+        if !self.style.0.is_empty() && !style_prop {
+            sep(buf)?;
+            let mut css = String::new();
+            self.style.format(f, &mut css)?;
+            f.write_attr(buf, "style", &css)?;
+        }
+        for (name, val) in self.on.0.entries(f.sort_attrs) {
+            let event_prop =
+                self.props.0.iter().any(|(prop, _)| {
+                    event_name(prop).is_some_and(|e| e.eq_ignore_ascii_case(name))
+                });
+            if event_prop {
+                continue;
+            }
+            sep(buf)?;
+            f.write_attr(buf, &format!("on{}", name), val)?;
         }
-        f.indent -= 1;
-        write!(buf, "{:pad$}</{}>", "", self.tag)?;
-        buf.push_str(f.line_sep);
         Ok(())
     }
+
+    /// value of a `style` prop when style is set too, the prop text if it has the same declarations,
+    /// as it does right after parsing, style if the prop is plain declarations, both otherwise
+    fn merged_style(&self, f: &TagFormatter, prop: &str) -> Result<String, std::fmt::Error> {
+        let declarations = split_style(prop);
+        if declarations.as_ref() == Some(&self.style.0) {
+            return Ok(prop.to_string());
+        }
+        let mut css = String::new();
+        if declarations.is_none() {
+            css.push_str(prop.trim_end());
+            css.push_str(if css.ends_with(';') { " " } else { "; " });
+        }
+        self.style.format(f, &mut css)?;
+        Ok(css)
+    }
+
+    /// code of the handler of `event`, names are case insensitive
+    fn handler(&self, event: &str) -> Option<&String> {
+        self.on
+            .0
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(event))
+            .map(|(_, code)| code)
+    }
+}
+
+/// event of an `on*` attribute name
+fn event_name(name: &str) -> Option<&str> {
+    name.get(..2)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("on"))
+        .map(|_| &name[2..])
+        .filter(|event| !event.is_empty())
 }
 
 impl Display for UnitTag {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut content = String::new();
         let mut formatter = TagFormatter::default();
-        self.format(&mut formatter, &mut content)?;
-        write!(f, "{}", content)
+        self.format(&mut formatter, f)
     }
 }
 
 impl<C: Into<Children>> From<(&'static str, C)> for UnitTag {
     fn from(src: (&'static str, C)) -> Self {
         Self {
-            tag: src.0,
+            tag: Cow::Borrowed(src.0),
             children: src.1.into().0,
             props: Default::default(),
             on: Default::default(),
@@ -166,7 +638,7 @@ impl<C: Into<Children>> From<(&'static str, C)> for UnitTag {
 impl<C: Into<Children>> From<(&'static str, TagProp, C)> for UnitTag {
     fn from(src: (&'static str, TagProp, C)) -> Self {
         Self {
-            tag: src.0,
+            tag: Cow::Borrowed(src.0),
             children: src.2.into().0,
             props: src.1,
             on: Default::default(),
@@ -178,7 +650,7 @@ impl<C: Into<Children>> From<(&'static str, TagProp, C)> for UnitTag {
 impl<C: Into<Children>> From<(&'static str, TagHandler, C)> for UnitTag {
     fn from(src: (&'static str, TagHandler, C)) -> Self {
         Self {
-            tag: src.0,
+            tag: Cow::Borrowed(src.0),
             children: src.2.into().0,
             props: Default::default(),
             on: src.1,
@@ -190,7 +662,7 @@ impl<C: Into<Children>> From<(&'static str, TagHandler, C)> for UnitTag {
 impl<C: Into<Children>> From<(&'static str, TagStyle, C)> for UnitTag {
     fn from(src: (&'static str, TagStyle, C)) -> Self {
         Self {
-            tag: src.0,
+            tag: Cow::Borrowed(src.0),
             children: src.2.into().0,
             props: Default::default(),
             on: Default::default(),
@@ -202,7 +674,7 @@ impl<C: Into<Children>> From<(&'static str, TagStyle, C)> for UnitTag {
 impl<C: Into<Children>> From<(&'static str, TagProp, TagHandler, C)> for UnitTag {
     fn from(src: (&'static str, TagProp, TagHandler, C)) -> Self {
         Self {
-            tag: src.0,
+            tag: Cow::Borrowed(src.0),
             children: src.3.into().0,
             style: Default::default(),
             props: src.1,
@@ -213,7 +685,7 @@ impl<C: Into<Children>> From<(&'static str, TagProp, TagHandler, C)> for UnitTag
 impl<C: Into<Children>> From<(&'static str, TagProp, TagStyle, C)> for UnitTag {
     fn from(src: (&'static str, TagProp, TagStyle, C)) -> Self {
         Self {
-            tag: src.0,
+            tag: Cow::Borrowed(src.0),
             children: src.3.into().0,
             on: Default::default(),
             props: src.1,
@@ -224,7 +696,7 @@ impl<C: Into<Children>> From<(&'static str, TagProp, TagStyle, C)> for UnitTag {
 impl<C: Into<Children>> From<(&'static str, TagHandler, TagProp, C)> for UnitTag {
     fn from(src: (&'static str, TagHandler, TagProp, C)) -> Self {
         Self {
-            tag: src.0,
+            tag: Cow::Borrowed(src.0),
             children: src.3.into().0,
             style: Default::default(),
             on: src.1,
@@ -235,7 +707,7 @@ impl<C: Into<Children>> From<(&'static str, TagHandler, TagProp, C)> for UnitTag
 impl<C: Into<Children>> From<(&'static str, TagHandler, TagStyle, C)> for UnitTag {
     fn from(src: (&'static str, TagHandler, TagStyle, C)) -> Self {
         Self {
-            tag: src.0,
+            tag: Cow::Borrowed(src.0),
             children: src.3.into().0,
             props: Default::default(),
             on: src.1,
@@ -246,7 +718,7 @@ impl<C: Into<Children>> From<(&'static str, TagHandler, TagStyle, C)> for UnitTa
 impl<C: Into<Children>> From<(&'static str, TagStyle, TagProp, C)> for UnitTag {
     fn from(src: (&'static str, TagStyle, TagProp, C)) -> Self {
         Self {
-            tag: src.0,
+            tag: Cow::Borrowed(src.0),
             children: src.3.into().0,
             on: Default::default(),
             style: src.1,
@@ -257,7 +729,7 @@ impl<C: Into<Children>> From<(&'static str, TagStyle, TagProp, C)> for UnitTag {
 impl<C: Into<Children>> From<(&'static str, TagStyle, TagHandler, C)> for UnitTag {
     fn from(src: (&'static str, TagStyle, TagHandler, C)) -> Self {
         Self {
-            tag: src.0,
+            tag: Cow::Borrowed(src.0),
             children: src.3.into().0,
             props: Default::default(),
             style: src.1,
@@ -269,7 +741,7 @@ impl<C: Into<Children>> From<(&'static str, TagStyle, TagHandler, C)> for UnitTa
 impl<C: Into<Children>> From<(&'static str, TagProp, TagHandler, TagStyle, C)> for UnitTag {
     fn from(src: (&'static str, TagProp, TagHandler, TagStyle, C)) -> Self {
         Self {
-            tag: src.0,
+            tag: Cow::Borrowed(src.0),
             children: src.4.into().0,
             props: src.1,
             on: src.2,
@@ -280,7 +752,7 @@ impl<C: Into<Children>> From<(&'static str, TagProp, TagHandler, TagStyle, C)> f
 impl<C: Into<Children>> From<(&'static str, TagProp, TagStyle, TagHandler, C)> for UnitTag {
     fn from(src: (&'static str, TagProp, TagStyle, TagHandler, C)) -> Self {
         Self {
-            tag: src.0,
+            tag: Cow::Borrowed(src.0),
             children: src.4.into().0,
             props: src.1,
             style: src.2,
@@ -291,7 +763,7 @@ impl<C: Into<Children>> From<(&'static str, TagProp, TagStyle, TagHandler, C)> f
 impl<C: Into<Children>> From<(&'static str, TagHandler, TagProp, TagStyle, C)> for UnitTag {
     fn from(src: (&'static str, TagHandler, TagProp, TagStyle, C)) -> Self {
         Self {
-            tag: src.0,
+            tag: Cow::Borrowed(src.0),
             children: src.4.into().0,
             on: src.1,
             props: src.2,
@@ -302,7 +774,7 @@ impl<C: Into<Children>> From<(&'static str, TagHandler, TagProp, TagStyle, C)> f
 impl<C: Into<Children>> From<(&'static str, TagHandler, TagStyle, TagProp, C)> for UnitTag {
     fn from(src: (&'static str, TagHandler, TagStyle, TagProp, C)) -> Self {
         Self {
-            tag: src.0,
+            tag: Cow::Borrowed(src.0),
             children: src.4.into().0,
             on: src.1,
             style: src.2,
@@ -313,7 +785,7 @@ impl<C: Into<Children>> From<(&'static str, TagHandler, TagStyle, TagProp, C)> f
 impl<C: Into<Children>> From<(&'static str, TagStyle, TagProp, TagHandler, C)> for UnitTag {
     fn from(src: (&'static str, TagStyle, TagProp, TagHandler, C)) -> Self {
         Self {
-            tag: src.0,
+            tag: Cow::Borrowed(src.0),
             children: src.4.into().0,
             style: src.1,
             props: src.2,
@@ -324,7 +796,7 @@ impl<C: Into<Children>> From<(&'static str, TagStyle, TagProp, TagHandler, C)> f
 impl<C: Into<Children>> From<(&'static str, TagStyle, TagHandler, TagProp, C)> for UnitTag {
     fn from(src: (&'static str, TagStyle, TagHandler, TagProp, C)) -> Self {
         Self {
-            tag: src.0,
+            tag: Cow::Borrowed(src.0),
             children: src.4.into().0,
             style: src.1,
             on: src.2,
@@ -334,6 +806,17 @@ impl<C: Into<Children>> From<(&'static str, TagStyle, TagHandler, TagProp, C)> f
 }
 
 impl UnitTag {
+    /// an empty element named `name`, which can be any string, such as a custom element name
+    pub fn new<N: Into<Cow<'static, str>>>(name: N) -> Self {
+        Self {
+            tag: name.into(),
+            props: Default::default(),
+            on: Default::default(),
+            style: Default::default(),
+            children: vec![],
+        }
+    }
+
     /// set tag properties
     pub fn props(mut self, props: TagProp) -> Self {
         self.props = props;
@@ -351,63 +834,168 @@ impl UnitTag {
         self.on = handlers;
         self
     }
-}
-
-pub fn x<T: Into<UnitTag>>(tag: T) -> UnitTag {
-    tag.into()
-}
 
-#[derive(Debug, Clone)]
-pub struct TagFormatter {
-    pub tab_size: usize,
-    pub indent: usize,
-    pub max_width: usize,
-    pub newline_on_prop: bool,
-    pub line_sep: &'static str,
-}
+    /// set attribute `name`, for names `prop!` does not accept
+    ///
+    /// # Panics
+    ///
+    /// if `name` can not be written as an attribute name, check it with [`is_valid_attr_name`]
+    pub fn attr<V: AttrValue>(mut self, name: &str, value: V) -> Self {
+        assert!(
+            is_valid_attr_name(name),
+            "invalid attribute name {:?}",
+            name
+        );
+        self.props.set(name, value.into_attr_named(name));
+        self
+    }
 
-impl Default for TagFormatter {
-    fn default() -> Self {
-        Self {
-            tab_size: 4,
-            indent: 0,
-            max_width: 120,
-            newline_on_prop: false,
-            line_sep: "\n",
+    /// add the generated name of `class` in `style` to the classes of the tag,
+    /// the stylesheet is written into head of the [`crate::Document`] if the tag is rendered,
+    /// an error if `style` does not use `class`
+    pub fn scoped(mut self, style: &ScopedStyle, class: &str) -> Result<Self, UnknownClass> {
+        let name = style
+            .class(class)
+            .ok_or_else(|| UnknownClass(class.to_string()))?;
+        match self.props.0.get_mut("class") {
+            Some(classes) if !classes.is_empty() => {
+                classes.push(' ');
+                classes.push_str(name);
+            }
+            _ => {
+                self.props.0.insert("class", name);
+            }
         }
+        if !self.style.1.iter().any(|s| Arc::ptr_eq(s, style.sheet())) {
+            self.style.1.push(style.sheet().clone());
+        }
+        Ok(self)
     }
 }
 
-impl TagFormatter {
-    pub fn pad_size(&self) -> usize {
-        self.indent * self.tab_size
-    }
+pub fn x<T: Into<UnitTag>>(tag: T) -> UnitTag {
+    tag.into()
 }
 
 /// a helper macro to define custom html tag construct function, struct and arguments structs
 ///
 /// ## example
 ///
-/// ```no_run
-/// tag!(app, App, AppArgs, "my custom tag");
+/// ```ignore
+/// use rtml::{tag, tags::*};
+///
+/// tag!(app, App, AppArgs, content = FlowContent, categories = [Flow], "my custom tag");
 ///
 /// let app = app(h1("great"));
 /// ```
 ///
+/// `content` is the content model in [`crate::content`] its children are checked against,
+/// `categories` are the content categories it belongs to
+///
+/// prefix the name with `void` for elements that can not have children,
+/// their construct function only accepts props, styles, handlers or `()`
+///
+/// ```ignore
+/// tag!(void icon, Icon, IconArgs, categories = [Flow, Phrasing], "my custom void tag");
+///
+/// let icon = icon(prop! { name = "flag" });
+/// ```
+///
 #[macro_export]
 macro_rules! tag {
-    ($func_name:ident, $struct:ident, $arg:ident, $($doc:literal),+) => {
+    (@element $func_name:ident, $struct:ident, $arg:ident, $model:ident, [$($cat:ident),*], $($doc:literal),+) => {
         $(#[doc=$doc])+
-        pub struct $struct (UnitTag);
+        pub struct $struct ($crate::tags::UnitTag);
+
+        $(impl $crate::content::$cat for $struct {})*
+
+        impl $struct {
+            /// tag name
+            pub const NAME: &'static str = stringify!($func_name);
+            /// content categories
+            pub const CATEGORIES: &'static [$crate::content::Category] = &[$($crate::content::Category::$cat),*];
+            /// runtime check of children against the content model
+            pub const PERMITS: fn(&dyn $crate::Tag) -> bool = <$crate::content::$model as $crate::content::ContentModel>::permits;
+
+            /// set attribute `name`, typed setters in [`attrs`]($crate::attrs) are preferred
+            ///
+            /// # Panics
+            ///
+            /// if `name` can not be written as an attribute name,
+            /// check it with [`is_valid_attr_name`]($crate::attrs::is_valid_attr_name)
+            pub fn attr<V: $crate::attrs::AttrValue>(mut self, name: &str, value: V) -> Self {
+                assert!($crate::attrs::is_valid_attr_name(name), "invalid attribute name {:?}", name);
+                self.0.props.set(name, value.into_attr_named(name));
+                self
+            }
+
+            /// add a scoped class, see [`ScopedStyle`]($crate::style::ScopedStyle)
+            pub fn scoped(
+                self,
+                style: &$crate::style::ScopedStyle,
+                class: &str,
+            ) -> Result<Self, $crate::style::UnknownClass> {
+                self.0.scoped(style, class).map(Self)
+            }
+        }
+
+        /// tree methods of [`UnitTag`], see [`tree`]($crate::tree)
+        impl std::ops::Deref for $struct {
+            type Target = $crate::tags::UnitTag;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl std::ops::DerefMut for $struct {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl $crate::attrs::GlobalAttrs for $struct {
+            fn props(&self) -> &$crate::tags::TagProp {
+                &self.0.props
+            }
+
+            fn props_mut(&mut self) -> &mut $crate::tags::TagProp {
+                &mut self.0.props
+            }
+        }
 
         impl $crate::Tag for $struct {
-            fn name(&self) -> &'static str {
+            fn name(&self) -> &str {
                 self.0.name()
             }
 
-            fn format(&self, f: &mut $crate::TagFormatter, buf: &mut String) -> std::fmt::Result {
+            fn format(&self, f: &mut $crate::tags::TagFormatter, buf: &mut dyn std::fmt::Write) -> std::fmt::Result {
                 self.0.format(f, buf)
             }
+
+            fn as_element(&self) -> Option<&$crate::tags::UnitTag> {
+                Some(&self.0)
+            }
+
+            fn as_element_mut(&mut self) -> Option<&mut $crate::tags::UnitTag> {
+                Some(&mut self.0)
+            }
+
+            fn as_any(&self) -> Option<&dyn std::any::Any> {
+                Some(self)
+            }
+
+            fn as_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
+                Some(self)
+            }
+
+            fn categories(&self) -> &'static [$crate::content::Category] {
+                Self::CATEGORIES
+            }
+
+            fn permits(&self, child: &dyn $crate::Tag) -> bool {
+                (Self::PERMITS)(child)
+            }
         }
 
         impl std::fmt::Display for $struct {
@@ -416,50 +1004,6 @@ macro_rules! tag {
             }
         }
 
-        // impl <C: Into<$crate::Children>> From<C> for $struct {
-        //     fn from(src: C) -> Self {
-        //         $struct ($crate::tags::UnitTag {
-        //             tag: stringify!($func_name),
-        //             props: Default::default(),
-        //             on: Default::default(),
-        //             style: Default::default(),
-        //             children: src.into().0,
-        //         })
-        //     }
-        // }
-
-        // impl<TA: $crate::Tag + 'static> From<TA> for $struct {
-        //     fn from(src: TA) -> Self {
-        //         let children: $crate::InnerChildren = vec![Box::new(src)];
-        //         Self($crate::tags::UnitTag {
-        //             tag: stringify!($func_name),
-        //             children: children.0,
-        //             props: Default::default(),
-        //             on: Default::default(),
-        //             style: Default::default()
-        //         })
-        //     }
-        // }
-
-        // impl From<$struct> for $crate::Children {
-        //     fn from(src: $struct) -> Self {
-        //         $crate::Children(vec![Box ::new(src)])
-        //     }
-        // }
-
-        impl<TA: $crate::Tag + 'static, TB: $crate::Tag + 'static> From<(TA, TB)> for $struct {
-            fn from(src: (TA, TB)) -> Self {
-                let children: $crate::Children = src.into();
-                Self($crate::tags::UnitTag {
-                    tag: stringify!($func_name),
-                    children: children.0,
-                    props: Default::default(),
-                    on: Default::default(),
-                    style: Default::default()
-                })
-            }
-        }
-
         pub struct $arg {
             pub children: $crate::InnerChildren,
             pub props: $crate::tags::TagProp,
@@ -467,7 +1011,41 @@ macro_rules! tag {
             pub style: $crate::tags::TagStyle,
         }
 
-        impl<C: Into<Children>> From<C> for $arg {
+        impl $struct {
+            /// set tag properties
+            pub fn props(mut self, props: $crate::tags::TagProp) -> Self {
+                self.0.props = props;
+                self
+            }
+
+            /// set tag styles
+            pub fn style(mut self, style: $crate::tags::TagStyle) -> Self {
+                self.0.style = style;
+                self
+            }
+
+            /// set tag handlers
+            pub fn on(mut self, handlers: $crate::tags::TagHandler) -> Self {
+                self.0.on = handlers;
+                self
+            }
+        }
+
+        $(#[doc=$doc])+
+        pub fn $func_name<T: Into<$arg>>(tag: T) -> $struct {
+            let args: $arg = tag.into();
+            let $arg { children, props, on, style } = args;
+            $struct($crate::tags::UnitTag {
+                tag: std::borrow::Cow::Borrowed(stringify!($func_name)),
+                children,
+                props,
+                on,
+                style
+            })
+        }
+    };
+    (@children $struct:ident, $func_name:ident, $arg:ident, $model:ident) => {
+        impl<C: $crate::content::Content<$crate::content::$model>> From<C> for $arg {
             fn from(src:  C) -> Self {
                 Self {
                     children: src.into().0,
@@ -478,8 +1056,8 @@ macro_rules! tag {
             }
         }
 
-        impl<C: Into<Children>> From<(TagProp, C)> for $arg {
-            fn from(src: ( TagProp, C)) -> Self {
+        impl<C: $crate::content::Content<$crate::content::$model>> From<($crate::tags::TagProp, C)> for $arg {
+            fn from(src: ( $crate::tags::TagProp, C)) -> Self {
                 Self {
                     children: src.1.into().0,
                     props: src.0,
@@ -489,8 +1067,8 @@ macro_rules! tag {
             }
         }
 
-        impl<C: Into<Children>> From<(TagHandler, C)> for $arg {
-            fn from(src: ( TagHandler, C)) -> Self {
+        impl<C: $crate::content::Content<$crate::content::$model>> From<($crate::tags::TagHandler, C)> for $arg {
+            fn from(src: ( $crate::tags::TagHandler, C)) -> Self {
                 Self {
                     children: src.1.into().0,
                     props: Default::default(),
@@ -500,8 +1078,8 @@ macro_rules! tag {
             }
         }
 
-        impl<C: Into<Children>> From<(TagStyle, C)> for $arg {
-            fn from(src: ( TagStyle, C)) -> Self {
+        impl<C: $crate::content::Content<$crate::content::$model>> From<($crate::tags::TagStyle, C)> for $arg {
+            fn from(src: ( $crate::tags::TagStyle, C)) -> Self {
                 Self {
                     children: src.1.into().0,
                     props: Default::default(),
@@ -511,8 +1089,8 @@ macro_rules! tag {
             }
         }
 
-        impl<C: Into<Children>> From<(TagProp, TagHandler, C)> for $arg {
-            fn from(src: ( TagProp, TagHandler, C)) -> Self {
+        impl<C: $crate::content::Content<$crate::content::$model>> From<($crate::tags::TagProp, $crate::tags::TagHandler, C)> for $arg {
+            fn from(src: ( $crate::tags::TagProp, $crate::tags::TagHandler, C)) -> Self {
                 Self {
                     children: src.2.into().0,
                     style: Default::default(),
@@ -521,8 +1099,8 @@ macro_rules! tag {
                 }
             }
         }
-        impl<C: Into<Children>> From<(TagProp, TagStyle, C)> for $arg {
-            fn from(src: ( TagProp, TagStyle, C)) -> Self {
+        impl<C: $crate::content::Content<$crate::content::$model>> From<($crate::tags::TagProp, $crate::tags::TagStyle, C)> for $arg {
+            fn from(src: ( $crate::tags::TagProp, $crate::tags::TagStyle, C)) -> Self {
                 Self {
                     children: src.2.into().0,
                     on: Default::default(),
@@ -531,8 +1109,8 @@ macro_rules! tag {
                 }
             }
         }
-        impl<C: Into<Children>> From<(TagHandler, TagProp, C)> for $arg {
-            fn from(src: ( TagHandler, TagProp, C)) -> Self {
+        impl<C: $crate::content::Content<$crate::content::$model>> From<($crate::tags::TagHandler, $crate::tags::TagProp, C)> for $arg {
+            fn from(src: ( $crate::tags::TagHandler, $crate::tags::TagProp, C)) -> Self {
                 Self {
                     children: src.2.into().0,
                     style: Default::default(),
@@ -541,8 +1119,8 @@ macro_rules! tag {
                 }
             }
         }
-        impl<C: Into<Children>> From<(TagHandler, TagStyle, C)> for $arg {
-            fn from(src: ( TagHandler, TagStyle, C)) -> Self {
+        impl<C: $crate::content::Content<$crate::content::$model>> From<($crate::tags::TagHandler, $crate::tags::TagStyle, C)> for $arg {
+            fn from(src: ( $crate::tags::TagHandler, $crate::tags::TagStyle, C)) -> Self {
                 Self {
                     children: src.2.into().0,
                     props: Default::default(),
@@ -551,8 +1129,8 @@ macro_rules! tag {
                 }
             }
         }
-        impl<C: Into<Children>> From<(TagStyle, TagProp, C)> for $arg {
-            fn from(src: ( TagStyle, TagProp, C)) -> Self {
+        impl<C: $crate::content::Content<$crate::content::$model>> From<($crate::tags::TagStyle, $crate::tags::TagProp, C)> for $arg {
+            fn from(src: ( $crate::tags::TagStyle, $crate::tags::TagProp, C)) -> Self {
                 Self {
                     children: src.2.into().0,
                     on: Default::default(),
@@ -561,8 +1139,8 @@ macro_rules! tag {
                 }
             }
         }
-        impl<C: Into<Children>> From<(TagStyle, TagHandler, C)> for $arg {
-            fn from(src: ( TagStyle, TagHandler, C)) -> Self {
+        impl<C: $crate::content::Content<$crate::content::$model>> From<($crate::tags::TagStyle, $crate::tags::TagHandler, C)> for $arg {
+            fn from(src: ( $crate::tags::TagStyle, $crate::tags::TagHandler, C)) -> Self {
                 Self {
                     children: src.2.into().0,
                     props: Default::default(),
@@ -572,8 +1150,8 @@ macro_rules! tag {
             }
         }
 
-        impl<C: Into<Children>> From<(TagProp, TagHandler, TagStyle, C)> for $arg {
-            fn from(src: ( TagProp, TagHandler, TagStyle, C)) -> Self {
+        impl<C: $crate::content::Content<$crate::content::$model>> From<($crate::tags::TagProp, $crate::tags::TagHandler, $crate::tags::TagStyle, C)> for $arg {
+            fn from(src: ( $crate::tags::TagProp, $crate::tags::TagHandler, $crate::tags::TagStyle, C)) -> Self {
                 Self {
                     children: src.3.into().0,
                     props: src.0,
@@ -582,8 +1160,8 @@ macro_rules! tag {
                 }
             }
         }
-        impl<C: Into<Children>> From<(TagProp, TagStyle, TagHandler, C)> for $arg {
-            fn from(src: ( TagProp, TagStyle, TagHandler, C)) -> Self {
+        impl<C: $crate::content::Content<$crate::content::$model>> From<($crate::tags::TagProp, $crate::tags::TagStyle, $crate::tags::TagHandler, C)> for $arg {
+            fn from(src: ( $crate::tags::TagProp, $crate::tags::TagStyle, $crate::tags::TagHandler, C)) -> Self {
                 Self {
                     children: src.3.into().0,
                     props: src.0,
@@ -592,8 +1170,8 @@ macro_rules! tag {
                 }
             }
         }
-        impl<C: Into<Children>> From<(TagHandler, TagProp, TagStyle, C)> for $arg {
-            fn from(src: ( TagHandler, TagProp, TagStyle, C)) -> Self {
+        impl<C: $crate::content::Content<$crate::content::$model>> From<($crate::tags::TagHandler, $crate::tags::TagProp, $crate::tags::TagStyle, C)> for $arg {
+            fn from(src: ( $crate::tags::TagHandler, $crate::tags::TagProp, $crate::tags::TagStyle, C)) -> Self {
                 Self {
                     children: src.3.into().0,
                     on: src.0,
@@ -602,8 +1180,8 @@ macro_rules! tag {
                 }
             }
         }
-        impl<C: Into<Children>> From<(TagHandler, TagStyle, TagProp, C)> for $arg {
-            fn from(src: ( TagHandler, TagStyle, TagProp, C)) -> Self {
+        impl<C: $crate::content::Content<$crate::content::$model>> From<($crate::tags::TagHandler, $crate::tags::TagStyle, $crate::tags::TagProp, C)> for $arg {
+            fn from(src: ( $crate::tags::TagHandler, $crate::tags::TagStyle, $crate::tags::TagProp, C)) -> Self {
                 Self {
                     children: src.3.into().0,
                     on: src.0,
@@ -612,8 +1190,8 @@ macro_rules! tag {
                 }
             }
         }
-        impl<C: Into<Children>> From<(TagStyle, TagProp, TagHandler, C)> for $arg {
-            fn from(src: ( TagStyle, TagProp, TagHandler, C)) -> Self {
+        impl<C: $crate::content::Content<$crate::content::$model>> From<($crate::tags::TagStyle, $crate::tags::TagProp, $crate::tags::TagHandler, C)> for $arg {
+            fn from(src: ( $crate::tags::TagStyle, $crate::tags::TagProp, $crate::tags::TagHandler, C)) -> Self {
                 Self {
                     children: src.3.into().0,
                     style: src.0,
@@ -622,8 +1200,8 @@ macro_rules! tag {
                 }
             }
         }
-        impl<C: Into<Children>> From<(TagStyle, TagHandler, TagProp, C)> for $arg {
-            fn from(src: ( TagStyle, TagHandler, TagProp, C)) -> Self {
+        impl<C: $crate::content::Content<$crate::content::$model>> From<($crate::tags::TagStyle, $crate::tags::TagHandler, $crate::tags::TagProp, C)> for $arg {
+            fn from(src: ( $crate::tags::TagStyle, $crate::tags::TagHandler, $crate::tags::TagProp, C)) -> Self {
                 Self {
                     children: src.3.into().0,
                     style: src.0,
@@ -632,199 +1210,186 @@ macro_rules! tag {
                 }
             }
         }
-
-        ////////////////// helper sep
-
-    impl From<TagProp> for $arg {
-        fn from(src: TagProp) -> Self {
-            Self {
-                children: vec![],
-                props: src,
-                on: Default::default(),
-                style: Default::default(),
+    };
+    (@props $arg:ident) => {
+        impl From<$crate::tags::TagProp> for $arg {
+            fn from(src: $crate::tags::TagProp) -> Self {
+                Self {
+                    children: vec![],
+                    props: src,
+                    on: Default::default(),
+                    style: Default::default(),
+                }
             }
         }
-    }
 
-    impl From<TagHandler> for $arg {
-        fn from(src: TagHandler) -> Self {
-            Self {
-                children: vec![],
-                props: Default::default(),
-                on: src,
-                style: Default::default(),
+        impl From<$crate::tags::TagHandler> for $arg {
+            fn from(src: $crate::tags::TagHandler) -> Self {
+                Self {
+                    children: vec![],
+                    props: Default::default(),
+                    on: src,
+                    style: Default::default(),
+                }
             }
         }
-    }
 
-    impl From<TagStyle> for $arg {
-        fn from(src:  TagStyle) -> Self {
-            Self {
-                children: vec![],
-                props: Default::default(),
-                on: Default::default(),
-                style: src,
+        impl From<$crate::tags::TagStyle> for $arg {
+            fn from(src:  $crate::tags::TagStyle) -> Self {
+                Self {
+                    children: vec![],
+                    props: Default::default(),
+                    on: Default::default(),
+                    style: src,
+                }
             }
         }
-    }
 
-    impl From<(TagProp, TagHandler)> for $arg {
-        fn from(src: ( TagProp, TagHandler)) -> Self {
-            Self {
-                children: vec![],
-                style: Default::default(),
-                props: src.0,
-                on: src.1,
+        impl From<($crate::tags::TagProp, $crate::tags::TagHandler)> for $arg {
+            fn from(src: ( $crate::tags::TagProp, $crate::tags::TagHandler)) -> Self {
+                Self {
+                    children: vec![],
+                    style: Default::default(),
+                    props: src.0,
+                    on: src.1,
+                }
             }
         }
-    }
-    impl From<(TagProp, TagStyle)> for $arg {
-        fn from(src: ( TagProp, TagStyle)) -> Self {
-            Self {
-                children: vec![],
-                on: Default::default(),
-                props: src.0,
-                style: src.1,
+        impl From<($crate::tags::TagProp, $crate::tags::TagStyle)> for $arg {
+            fn from(src: ( $crate::tags::TagProp, $crate::tags::TagStyle)) -> Self {
+                Self {
+                    children: vec![],
+                    on: Default::default(),
+                    props: src.0,
+                    style: src.1,
+                }
             }
         }
-    }
-    impl From<(TagHandler, TagProp)> for $arg {
-        fn from(src: ( TagHandler, TagProp)) -> Self {
-            Self {
-                children: vec![],
-                style: Default::default(),
-                on: src.0,
-                props: src.1,
+        impl From<($crate::tags::TagHandler, $crate::tags::TagProp)> for $arg {
+            fn from(src: ( $crate::tags::TagHandler, $crate::tags::TagProp)) -> Self {
+                Self {
+                    children: vec![],
+                    style: Default::default(),
+                    on: src.0,
+                    props: src.1,
+                }
             }
         }
-    }
-    impl From<(TagHandler, TagStyle)> for $arg {
-        fn from(src: ( TagHandler, TagStyle)) -> Self {
-            Self {
-                children: vec![],
-                props: Default::default(),
-                on: src.0,
-                style: src.1,
+        impl From<($crate::tags::TagHandler, $crate::tags::TagStyle)> for $arg {
+            fn from(src: ( $crate::tags::TagHandler, $crate::tags::TagStyle)) -> Self {
+                Self {
+                    children: vec![],
+                    props: Default::default(),
+                    on: src.0,
+                    style: src.1,
+                }
             }
         }
-    }
-    impl From<(TagStyle, TagProp)> for $arg {
-        fn from(src: ( TagStyle, TagProp)) -> Self {
-            Self {
-                children: vec![],
-                on: Default::default(),
-                style: src.0,
-                props: src.1,
+        impl From<($crate::tags::TagStyle, $crate::tags::TagProp)> for $arg {
+            fn from(src: ( $crate::tags::TagStyle, $crate::tags::TagProp)) -> Self {
+                Self {
+                    children: vec![],
+                    on: Default::default(),
+                    style: src.0,
+                    props: src.1,
+                }
             }
         }
-    }
-    impl From<(TagStyle, TagHandler)> for $arg {
-        fn from(src: ( TagStyle, TagHandler)) -> Self {
-            Self {
-                children: vec![],
-                props: Default::default(),
-                style: src.0,
-                on: src.1,
+        impl From<($crate::tags::TagStyle, $crate::tags::TagHandler)> for $arg {
+            fn from(src: ( $crate::tags::TagStyle, $crate::tags::TagHandler)) -> Self {
+                Self {
+                    children: vec![],
+                    props: Default::default(),
+                    style: src.0,
+                    on: src.1,
+                }
             }
         }
-    }
 
-    impl From<(TagProp, TagHandler, TagStyle)> for $arg {
-        fn from(src: ( TagProp, TagHandler, TagStyle)) -> Self {
-            Self {
-                children: vec![],
-                props: src.0,
-                on: src.1,
-                style: src.2,
+        impl From<($crate::tags::TagProp, $crate::tags::TagHandler, $crate::tags::TagStyle)> for $arg {
+            fn from(src: ( $crate::tags::TagProp, $crate::tags::TagHandler, $crate::tags::TagStyle)) -> Self {
+                Self {
+                    children: vec![],
+                    props: src.0,
+                    on: src.1,
+                    style: src.2,
+                }
             }
         }
-    }
-    impl From<(TagProp, TagStyle, TagHandler)> for $arg {
-        fn from(src: ( TagProp, TagStyle, TagHandler)) -> Self {
-            Self {
-                children: vec![],
-                props: src.0,
-                style: src.1,
-                on: src.2,
+        impl From<($crate::tags::TagProp, $crate::tags::TagStyle, $crate::tags::TagHandler)> for $arg {
+            fn from(src: ( $crate::tags::TagProp, $crate::tags::TagStyle, $crate::tags::TagHandler)) -> Self {
+                Self {
+                    children: vec![],
+                    props: src.0,
+                    style: src.1,
+                    on: src.2,
+                }
             }
         }
-    }
-    impl From<(TagHandler, TagProp, TagStyle)> for $arg {
-        fn from(src: ( TagHandler, TagProp, TagStyle)) -> Self {
-            Self {
-                children: vec![],
-                on: src.0,
-                props: src.1,
-                style: src.2,
+        impl From<($crate::tags::TagHandler, $crate::tags::TagProp, $crate::tags::TagStyle)> for $arg {
+            fn from(src: ( $crate::tags::TagHandler, $crate::tags::TagProp, $crate::tags::TagStyle)) -> Self {
+                Self {
+                    children: vec![],
+                    on: src.0,
+                    props: src.1,
+                    style: src.2,
+                }
             }
         }
-    }
-    impl From<(TagHandler, TagStyle, TagProp)> for $arg {
-        fn from(src: ( TagHandler, TagStyle, TagProp)) -> Self {
-            Self {
-                children: vec![],
-                on: src.0,
-                style: src.1,
-                props: src.2,
+        impl From<($crate::tags::TagHandler, $crate::tags::TagStyle, $crate::tags::TagProp)> for $arg {
+            fn from(src: ( $crate::tags::TagHandler, $crate::tags::TagStyle, $crate::tags::TagProp)) -> Self {
+                Self {
+                    children: vec![],
+                    on: src.0,
+                    style: src.1,
+                    props: src.2,
+                }
             }
         }
-    }
-    impl From<(TagStyle, TagProp, TagHandler)> for $arg {
-        fn from(src: ( TagStyle, TagProp, TagHandler)) -> Self {
-            Self {
-                children: vec![],
-                style: src.0,
-                props: src.1,
-                on: src.2,
+        impl From<($crate::tags::TagStyle, $crate::tags::TagProp, $crate::tags::TagHandler)> for $arg {
+            fn from(src: ( $crate::tags::TagStyle, $crate::tags::TagProp, $crate::tags::TagHandler)) -> Self {
+                Self {
+                    children: vec![],
+                    style: src.0,
+                    props: src.1,
+                    on: src.2,
+                }
             }
         }
-    }
-    impl From<(TagStyle, TagHandler, TagProp)> for $arg {
-        fn from(src: ( TagStyle, TagHandler, TagProp)) -> Self {
-            Self {
-                children: vec![],
-                style: src.0,
-                on: src.1,
-                props: src.2,
+        impl From<($crate::tags::TagStyle, $crate::tags::TagHandler, $crate::tags::TagProp)> for $arg {
+            fn from(src: ( $crate::tags::TagStyle, $crate::tags::TagHandler, $crate::tags::TagProp)) -> Self {
+                Self {
+                    children: vec![],
+                    style: src.0,
+                    on: src.1,
+                    props: src.2,
+                }
             }
         }
-    }
-
-
-        impl $struct {
-            /// set tag properties
-            pub fn props(mut self, props: $crate::tags::TagProp) -> Self {
-                self.0.props = props;
-                self
-            }
-
-            /// set tag styles
-            pub fn style(mut self, style: $crate::tags::TagStyle) -> Self {
-                self.0.style = style;
-                self
-            }
+    };
+    (void $func_name:ident, $struct:ident, $arg:ident, categories = [$($cat:ident),*], $($doc:literal),+) => {
+        $crate::tag!(@element $func_name, $struct, $arg, NoContent, [$($cat),*], $($doc),+);
+        $crate::tag!(@props $arg);
 
-            /// set tag handlers
-            pub fn on(mut self, handlers: $crate::tags::TagHandler) -> Self {
-                self.0.on = handlers;
-                self
+        impl From<()> for $arg {
+            fn from(_: ()) -> Self {
+                Self {
+                    children: vec![],
+                    props: Default::default(),
+                    on: Default::default(),
+                    style: Default::default(),
+                }
             }
         }
-
-        $(#[doc=$doc])+
-        pub fn $func_name<T: Into<$arg>>(tag: T) -> $struct {
-            let args: $arg = tag.into();
-            let $arg { children, props, on, style } = args;
-            $struct($crate::tags::UnitTag {
-                tag: stringify!($func_name),
-                children,
-                props,
-                on,
-                style
-            })
-        }
-
-
+    };
+    (
+        $func_name:ident, $struct:ident, $arg:ident,
+        content = $model:ident, categories = [$($cat:ident),*],
+        $($doc:literal),+
+    ) => {
+        $crate::tag!(@element $func_name, $struct, $arg, $model, [$($cat),*], $($doc),+);
+        $crate::tag!(@children $struct, $func_name, $arg, $model);
+        $crate::tag!(@props $arg);
     };
 }
 
@@ -832,6 +1397,8 @@ tag!(
     a,
     A,
     AArgs,
+    content = FlowNoInteractive,
+    categories = [Flow, Phrasing, Interactive],
     r#""#,
     r#"`<a>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/a)"#,
     r#""#,
@@ -849,6 +1416,8 @@ tag!(
     abbr,
     Abbr,
     AbbrArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<abbr>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/abbr)"#,
     r#""#,
@@ -866,6 +1435,8 @@ tag!(
     address,
     Address,
     AddressArgs,
+    content = FlowContent,
+    categories = [Flow, NonInteractive],
     r#""#,
     r#"`<address>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/address)"#,
     r#""#,
@@ -880,9 +1451,10 @@ tag!(
     r#"- [dom API](https://developer.mozilla.org/zh-CN/docs/Web/API/HTMLElement)"#
 );
 tag!(
-    area,
+    void area,
     Area,
     AreaArgs,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<area>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/area)"#,
     r#""#,
@@ -900,6 +1472,8 @@ tag!(
     article,
     Article,
     ArticleArgs,
+    content = FlowContent,
+    categories = [Flow, Sectioning, NonInteractive],
     r#""#,
     r#"`<article>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/article)"#,
     r#""#,
@@ -917,6 +1491,8 @@ tag!(
     aside,
     Aside,
     AsideArgs,
+    content = FlowContent,
+    categories = [Flow, Sectioning, NonInteractive],
     r#""#,
     r#"`<aside>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/aside)"#,
     r#""#,
@@ -934,6 +1510,8 @@ tag!(
     audio,
     Audio,
     AudioArgs,
+    content = MediaContent,
+    categories = [Flow, Phrasing, Embedded, Interactive],
     r#""#,
     r#"`<audio>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/audio)"#,
     r#""#,
@@ -951,6 +1529,8 @@ tag!(
     b,
     B,
     BArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#"- 允许的内容"#,
     r#"<a href="/en-US/docs/Web/Guide/HTML/Content_categories#phrasing_content">Phrasing content</a>."#,
     r#"- 忽略结束标签"#,
@@ -960,9 +1540,10 @@ tag!(
     r#"- [dom API](https://developer.mozilla.org/zh-CN/docs/Web/API/HTMLElement)"#
 );
 tag!(
-    base,
+    void base,
     Base,
     BaseArgs,
+    categories = [Metadata],
     r#"- 允许的内容"#,
     r#"无，它是一个<a href="/zh-CN/docs/Glossary/Empty_element">empty element</a>"#,
     r#"- 忽略结束标签"#,
@@ -975,6 +1556,8 @@ tag!(
     bdi,
     Bdi,
     BdiArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#"- 允许的内容"#,
     r#"<a title="HTML/Content_categories#Flow_content" href="/en-US/docs/Web/Guide/HTML/Content_categories#phrasing_content">Phrasing content</a>."#,
     r#"- 忽略结束标签"#,
@@ -987,6 +1570,8 @@ tag!(
     bdo,
     Bdo,
     BdoArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<bdo>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/bdo)"#,
     r#""#,
@@ -1004,6 +1589,8 @@ tag!(
     blockquote,
     Blockquote,
     BlockquoteArgs,
+    content = FlowContent,
+    categories = [Flow, NonInteractive],
     r#""#,
     r#"`<blockquote>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/blockquote)"#,
     r#""#,
@@ -1021,6 +1608,8 @@ tag!(
     body,
     Body,
     BodyArgs,
+    content = FlowContent,
+    categories = [],
     r#""#,
     r#"`<body>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/body)"#,
     r#""#,
@@ -1035,9 +1624,10 @@ tag!(
     r#"- [dom API](https://developer.mozilla.org/zh-CN/docs/Web/API/HTMLBodyElement)"#
 );
 tag!(
-    br,
+    void br,
     Br,
     BrArgs,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<br>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/br)"#,
     r#""#,
@@ -1055,6 +1645,8 @@ tag!(
     button,
     Button,
     ButtonArgs,
+    content = PhrasingNoInteractive,
+    categories = [Flow, Phrasing, Interactive],
     r#""#,
     r#"`<button>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/button)"#,
     r#""#,
@@ -1072,6 +1664,8 @@ tag!(
     canvas,
     Canvas,
     CanvasArgs,
+    content = FlowContent,
+    categories = [Flow, Phrasing, Embedded, NonInteractive],
     r#""#,
     r#"`<canvas>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/canvas)"#,
     r#""#,
@@ -1089,6 +1683,8 @@ tag!(
     caption,
     Caption,
     CaptionArgs,
+    content = FlowContent,
+    categories = [],
     r#""#,
     r#"`<caption>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/caption)"#,
     r#""#,
@@ -1106,6 +1702,8 @@ tag!(
     cite,
     Cite,
     CiteArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<cite>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/cite)"#,
     r#""#,
@@ -1123,6 +1721,8 @@ tag!(
     code,
     Code,
     CodeArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<code>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/code)"#,
     r#""#,
@@ -1137,9 +1737,10 @@ tag!(
     r#"- [dom API](https://developer.mozilla.org/zh-CN/docs/Web/API/HTMLElement)"#
 );
 tag!(
-    col,
+    void col,
     Col,
     ColArgs,
+    categories = [],
     r#""#,
     r#"`<col>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/col)"#,
     r#""#,
@@ -1157,6 +1758,8 @@ tag!(
     colgroup,
     Colgroup,
     ColgroupArgs,
+    content = ColgroupContent,
+    categories = [],
     r#""#,
     r#"`<colgroup>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/colgroup)"#,
     r#""#,
@@ -1176,6 +1779,8 @@ tag!(
     content,
     Content,
     ContentArgs,
+    content = FlowContent,
+    categories = [],
     r#""#,
     r#"`<content>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/content)"#,
     r#""#,
@@ -1193,6 +1798,8 @@ tag!(
     data,
     Data,
     DataArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<data>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/data)"#,
     r#""#,
@@ -1210,6 +1817,8 @@ tag!(
     datalist,
     Datalist,
     DatalistArgs,
+    content = DatalistContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<datalist>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/datalist)"#,
     r#""#,
@@ -1227,6 +1836,8 @@ tag!(
     dd,
     Dd,
     DdArgs,
+    content = FlowContent,
+    categories = [],
     r#""#,
     r#"`<dd>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/dd)"#,
     r#""#,
@@ -1244,6 +1855,8 @@ tag!(
     del,
     Del,
     DelArgs,
+    content = FlowContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<del>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/del)"#,
     r#""#,
@@ -1261,6 +1874,8 @@ tag!(
     details,
     Details,
     DetailsArgs,
+    content = DetailsContent,
+    categories = [Flow, Interactive],
     r#""#,
     r#"`<details>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/details)"#,
     r#""#,
@@ -1278,6 +1893,8 @@ tag!(
     dfn,
     Dfn,
     DfnArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<dfn>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/dfn)"#,
     r#""#,
@@ -1295,6 +1912,8 @@ tag!(
     dialog,
     Dialog,
     DialogArgs,
+    content = FlowContent,
+    categories = [Flow, NonInteractive],
     r#""#,
     r#"`<dialog>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/dialog)"#,
     r#""#,
@@ -1312,6 +1931,8 @@ tag!(
     div,
     Div,
     DivArgs,
+    content = FlowContent,
+    categories = [Flow, NonInteractive],
     r#""#,
     r#"`<div>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/div)"#,
     r#""#,
@@ -1331,6 +1952,8 @@ tag!(
     dl,
     Dl,
     DlArgs,
+    content = DlContent,
+    categories = [Flow, NonInteractive],
     r#""#,
     r#"`<dl>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/dl)"#,
     r#""#,
@@ -1348,6 +1971,8 @@ tag!(
     dt,
     Dt,
     DtArgs,
+    content = FlowContent,
+    categories = [],
     r#""#,
     r#"`<dt>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/dt)"#,
     r#""#,
@@ -1365,6 +1990,8 @@ tag!(
     em,
     Em,
     EmArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<em>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/em)"#,
     r#""#,
@@ -1378,11 +2005,19 @@ tag!(
     r#"<dfn>Permitted parent elements</dfn> Any element that accepts <a href="/en-US/docs/Web/Guide/HTML/Content_categories#phrasing_content">phrasing content</a>."#,
     r#"- [dom API](https://developer.mozilla.org/zh-CN/docs/Web/API/HTMLElement)"#
 );
-tag!(embed, Embed, EmbedArgs, "");
+tag!(
+    void embed,
+    Embed,
+    EmbedArgs,
+    categories = [Flow, Phrasing, Embedded, Interactive],
+    r#"`<embed>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/embed)"#
+);
 tag!(
     fieldset,
     Fieldset,
     FieldsetArgs,
+    content = FieldsetContent,
+    categories = [Flow, NonInteractive],
     r#""#,
     r#"`<fieldset>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/fieldset)"#,
     r#""#,
@@ -1400,6 +2035,8 @@ tag!(
     figcaption,
     Figcaption,
     FigcaptionArgs,
+    content = FlowContent,
+    categories = [],
     r#"- 允许的内容"#,
     r#"<a href="/en-US/docs/Web/Guide/HTML/Content_categories#flow_content" title="HTML/Content categories#Flow content">流式内容</a>"#,
     r#"- 忽略结束标签"#,
@@ -1412,6 +2049,8 @@ tag!(
     figure,
     Figure,
     FigureArgs,
+    content = FigureContent,
+    categories = [Flow, NonInteractive],
     r#""#,
     r#"`<figure>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/figure)"#,
     r#""#,
@@ -1429,6 +2068,8 @@ tag!(
     footer,
     Footer,
     FooterArgs,
+    content = FlowContent,
+    categories = [Flow, NonInteractive],
     r#""#,
     r#"`<footer>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/footer)"#,
     r#""#,
@@ -1446,6 +2087,8 @@ tag!(
     form,
     Form,
     FormArgs,
+    content = FlowContent,
+    categories = [Flow, NonInteractive],
     r#""#,
     r#"`<form>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/form)"#,
     r#""#,
@@ -1463,6 +2106,8 @@ tag!(
     h1,
     H1,
     H1Args,
+    content = PhrasingContent,
+    categories = [Flow, Heading, NonInteractive],
     r#""#,
     r#"`<h1>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/h1)"#,
     r#""#,
@@ -1484,6 +2129,8 @@ tag!(
     h2,
     H2,
     H2Args,
+    content = PhrasingContent,
+    categories = [Flow, Heading, NonInteractive],
     r#""#,
     r#"`<h2>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/h2)"#,
     r#""#,
@@ -1505,6 +2152,8 @@ tag!(
     h3,
     H3,
     H3Args,
+    content = PhrasingContent,
+    categories = [Flow, Heading, NonInteractive],
     r#""#,
     r#"`<h3>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/h3)"#,
     r#""#,
@@ -1526,6 +2175,8 @@ tag!(
     h4,
     H4,
     H4Args,
+    content = PhrasingContent,
+    categories = [Flow, Heading, NonInteractive],
     r#""#,
     r#"`<h4>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/h4)"#,
     r#""#,
@@ -1547,6 +2198,8 @@ tag!(
     h5,
     H5,
     H5Args,
+    content = PhrasingContent,
+    categories = [Flow, Heading, NonInteractive],
     r#""#,
     r#"`<h5>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/h5)"#,
     r#""#,
@@ -1568,6 +2221,8 @@ tag!(
     h6,
     H6,
     H6Args,
+    content = PhrasingContent,
+    categories = [Flow, Heading, NonInteractive],
     r#""#,
     r#"`<h6>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/h6)"#,
     r#""#,
@@ -1589,6 +2244,8 @@ tag!(
     head,
     Head,
     HeadArgs,
+    content = MetadataContent,
+    categories = [],
     r#""#,
     r#"`<head>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/head)"#,
     r#""#,
@@ -1606,6 +2263,8 @@ tag!(
     header,
     Header,
     HeaderArgs,
+    content = FlowContent,
+    categories = [Flow, NonInteractive],
     r#""#,
     r#"`<header>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/header)"#,
     r#""#,
@@ -1620,9 +2279,10 @@ tag!(
     r#"- [dom API](https://developer.mozilla.org/zh-CN/docs/Web/API/HTMLElement)"#
 );
 tag!(
-    hr,
+    void hr,
     Hr,
     HrArgs,
+    categories = [Flow, NonInteractive],
     r#""#,
     r#"`<hr>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/hr)"#,
     r#""#,
@@ -1640,6 +2300,8 @@ tag!(
     html,
     Html,
     HtmlArgs,
+    content = HtmlContent,
+    categories = [],
     r#""#,
     r#"`<html>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/html)"#,
     r#""#,
@@ -1657,6 +2319,8 @@ tag!(
     i,
     I,
     IArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<i>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/i)"#,
     r#""#,
@@ -1674,6 +2338,8 @@ tag!(
     iframe,
     Iframe,
     IframeArgs,
+    content = NoContent,
+    categories = [Flow, Phrasing, Embedded, Interactive],
     r#""#,
     r#"`<iframe>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/iframe)"#,
     r#""#,
@@ -1688,9 +2354,10 @@ tag!(
     r#"- [dom API]()"#
 );
 tag!(
-    img,
+    void img,
     Img,
     ImgArgs,
+    categories = [Flow, Phrasing, Embedded, NonInteractive],
     r#""#,
     r#"`<img>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/img)"#,
     r#""#,
@@ -1705,9 +2372,10 @@ tag!(
     r#"- [dom API](https://developer.mozilla.org/zh-CN/docs/Web/API/HTMLImageElement)"#
 );
 tag!(
-    input,
+    void input,
     Input,
     InputArgs,
+    categories = [Flow, Phrasing, Interactive],
     r#""#,
     r#"`<input>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/input)"#,
     r#""#,
@@ -1726,6 +2394,8 @@ tag!(
     ins,
     Ins,
     InsArgs,
+    content = FlowContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<ins>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/ins)"#,
     r#""#,
@@ -1743,6 +2413,8 @@ tag!(
     kbd,
     Kbd,
     KbdArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<kbd>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/kbd)"#,
     r#""#,
@@ -1760,6 +2432,8 @@ tag!(
     label,
     Label,
     LabelArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, Interactive],
     r#""#,
     r#"`<label>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/label)"#,
     r#""#,
@@ -1777,6 +2451,8 @@ tag!(
     legend,
     Legend,
     LegendArgs,
+    content = PhrasingContent,
+    categories = [],
     r#""#,
     r#"`<legend>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/legend)"#,
     r#""#,
@@ -1794,6 +2470,8 @@ tag!(
     li,
     Li,
     LiArgs,
+    content = FlowContent,
+    categories = [],
     r#""#,
     r#"`<li>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/li)"#,
     r#""#,
@@ -1808,9 +2486,10 @@ tag!(
     r#"- [dom API]()"#
 );
 tag!(
-    link,
+    void link,
     Link,
     LinkArgs,
+    categories = [Metadata, Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<link>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/link)"#,
     r#""#,
@@ -1828,6 +2507,8 @@ tag!(
     main,
     Main,
     MainArgs,
+    content = FlowContent,
+    categories = [Flow, NonInteractive],
     r#""#,
     r#"`<main>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/main)"#,
     r#""#,
@@ -1845,6 +2526,8 @@ tag!(
     map,
     Map,
     MapArgs,
+    content = FlowContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<map>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/map)"#,
     r#""#,
@@ -1862,6 +2545,8 @@ tag!(
     mark,
     Mark,
     MarkArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<mark>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/mark)"#,
     r#""#,
@@ -1879,6 +2564,8 @@ tag!(
     menu,
     Menu,
     MenuArgs,
+    content = ListContent,
+    categories = [Flow, NonInteractive],
     r#""#,
     r#"`<menu>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/menu)"#,
     r#""#,
@@ -1894,9 +2581,10 @@ tag!(
     r#"- [dom API](https://developer.mozilla.org/en-US/docs/Web/API/HTMLMenuElement)"#
 );
 tag!(
-    meta,
+    void meta,
     Meta,
     MetaArgs,
+    categories = [Metadata],
     r#""#,
     r#"`<meta>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/meta)"#,
     r#""#,
@@ -1914,6 +2602,8 @@ tag!(
     meter,
     Meter,
     MeterArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<meter>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/meter)"#,
     r#""#,
@@ -1931,6 +2621,8 @@ tag!(
     nav,
     Nav,
     NavArgs,
+    content = FlowContent,
+    categories = [Flow, Sectioning, NonInteractive],
     r#""#,
     r#"`<nav>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/nav)"#,
     r#""#,
@@ -1948,6 +2640,8 @@ tag!(
     noscript,
     Noscript,
     NoscriptArgs,
+    content = FlowContent,
+    categories = [Metadata, Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<noscript>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/noscript)"#,
     r#""#,
@@ -1967,6 +2661,8 @@ tag!(
     object,
     Object,
     ObjectArgs,
+    content = FlowContent,
+    categories = [Flow, Phrasing, Embedded, NonInteractive],
     r#""#,
     r#"`<object>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/object)"#,
     r#""#,
@@ -1984,6 +2680,8 @@ tag!(
     ol,
     Ol,
     OlArgs,
+    content = ListContent,
+    categories = [Flow, NonInteractive],
     r#""#,
     r#"`<ol>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/ol)"#,
     r#""#,
@@ -2001,6 +2699,8 @@ tag!(
     optgroup,
     Optgroup,
     OptgroupArgs,
+    content = OptgroupContent,
+    categories = [],
     r#""#,
     r#"`<optgroup>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/optgroup)"#,
     r#""#,
@@ -2018,6 +2718,8 @@ tag!(
     option,
     Option_,
     OptionArgs,
+    content = TextContent,
+    categories = [],
     r#""#,
     r#"`<option>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/option)"#,
     r#""#,
@@ -2035,6 +2737,8 @@ tag!(
     output,
     Output,
     OutputArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<output>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/output)"#,
     r#""#,
@@ -2052,6 +2756,8 @@ tag!(
     p,
     P,
     PArgs,
+    content = PhrasingContent,
+    categories = [Flow, NonInteractive],
     r#"- 允许的内容"#,
     r#"<dfn>允许的内容</dfn> <a href="/en-US/docs/Web/Guide/HTML/Content_categories#phrasing_content">Phrasing content</a>."#,
     r#"- 忽略结束标签"#,
@@ -2064,6 +2770,8 @@ tag!(
     picture,
     Picture,
     PictureArgs,
+    content = PictureContent,
+    categories = [Flow, Phrasing, Embedded, NonInteractive],
     r#""#,
     r#"`<picture>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/picture)"#,
     r#""#,
@@ -2081,6 +2789,8 @@ tag!(
     portal,
     Portal,
     PortalArgs,
+    content = NoContent,
+    categories = [Flow, Phrasing, Embedded, NonInteractive],
     r#""#,
     r#"`<portal>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/portal)"#,
     r#""#,
@@ -2098,6 +2808,8 @@ tag!(
     pre,
     Pre,
     PreArgs,
+    content = PhrasingContent,
+    categories = [Flow, NonInteractive],
     r#""#,
     r#"`<pre>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/pre)"#,
     r#""#,
@@ -2115,6 +2827,8 @@ tag!(
     progress,
     Progress,
     ProgressArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<progress>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/progress)"#,
     r#""#,
@@ -2132,6 +2846,8 @@ tag!(
     q,
     Q,
     QArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<q>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/q)"#,
     r#""#,
@@ -2149,6 +2865,8 @@ tag!(
     rp,
     Rp,
     RpArgs,
+    content = TextContent,
+    categories = [],
     r#""#,
     r#"`<rp>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/rp)"#,
     r#""#,
@@ -2166,6 +2884,8 @@ tag!(
     rt,
     Rt,
     RtArgs,
+    content = PhrasingContent,
+    categories = [],
     r#""#,
     r#"`<rt>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/rt)"#,
     r#""#,
@@ -2183,6 +2903,8 @@ tag!(
     ruby,
     Ruby,
     RubyArgs,
+    content = RubyContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<ruby>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/ruby)"#,
     r#""#,
@@ -2200,6 +2922,8 @@ tag!(
     s,
     S,
     SArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<s>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/s)"#,
     r#""#,
@@ -2217,6 +2941,8 @@ tag!(
     samp,
     Samp,
     SampArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<samp>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/samp)"#,
     r#""#,
@@ -2234,6 +2960,8 @@ tag!(
     script,
     Script,
     ScriptArgs,
+    content = TextContent,
+    categories = [Metadata, Flow, Phrasing, ScriptSupporting, NonInteractive],
     r#""#,
     r#"`<script>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/script)"#,
     r#""#,
@@ -2251,6 +2979,8 @@ tag!(
     section,
     Section,
     SectionArgs,
+    content = FlowContent,
+    categories = [Flow, Sectioning, NonInteractive],
     r#""#,
     r#"`<section>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/section)"#,
     r#""#,
@@ -2268,6 +2998,8 @@ tag!(
     select,
     Select,
     SelectArgs,
+    content = SelectContent,
+    categories = [Flow, Phrasing, Interactive],
     r#""#,
     r#"`<select>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/select)"#,
     r#""#,
@@ -2285,6 +3017,8 @@ tag!(
     shadow,
     Shadow,
     ShadowArgs,
+    content = FlowContent,
+    categories = [],
     r#""#,
     r#"`<shadow>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/shadow)"#,
     r#""#,
@@ -2302,6 +3036,8 @@ tag!(
     slot,
     Slot,
     SlotArgs,
+    content = FlowContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<slot>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/slot)"#,
     r#""#,
@@ -2319,6 +3055,8 @@ tag!(
     small,
     Small,
     SmallArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<small>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/small)"#,
     r#""#,
@@ -2333,9 +3071,10 @@ tag!(
     r#"- [dom API]()"#
 );
 tag!(
-    source,
+    void source,
     Source,
     SourceArgs,
+    categories = [],
     r#""#,
     r#"`<source>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/source)"#,
     r#""#,
@@ -2353,6 +3092,8 @@ tag!(
     span,
     Span,
     SpanArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<span>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/span)"#,
     r#""#,
@@ -2370,6 +3111,8 @@ tag!(
     strong,
     Strong,
     StrongArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<strong>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/strong)"#,
     r#""#,
@@ -2387,6 +3130,8 @@ tag!(
     style,
     Style,
     StyleArgs,
+    content = TextContent,
+    categories = [Metadata],
     r#""#,
     r#"`<style>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/style)"#,
     r#""#,
@@ -2404,6 +3149,8 @@ tag!(
     sub,
     Sub,
     SubArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<sub>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/sub)"#,
     r#""#,
@@ -2421,6 +3168,8 @@ tag!(
     summary,
     Summary,
     SummaryArgs,
+    content = PhrasingContent,
+    categories = [],
     r#""#,
     r#"`<summary>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/summary)"#,
     r#""#,
@@ -2438,6 +3187,8 @@ tag!(
     sup,
     Sup,
     SupArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<sup>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/sup)"#,
     r#""#,
@@ -2455,6 +3206,8 @@ tag!(
     table,
     Table,
     TableArgs,
+    content = TableContent,
+    categories = [Flow, NonInteractive],
     r#""#,
     r#"`<table>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/table)"#,
     r#""#,
@@ -2487,6 +3240,8 @@ tag!(
     tbody,
     Tbody,
     TbodyArgs,
+    content = TableSectionContent,
+    categories = [],
     r#""#,
     r#"`<tbody>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/tbody)"#,
     r#""#,
@@ -2510,6 +3265,8 @@ tag!(
     td,
     Td,
     TdArgs,
+    content = FlowContent,
+    categories = [],
     r#""#,
     r#"`<td>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/td)"#,
     r#""#,
@@ -2528,6 +3285,8 @@ tag!(
     template,
     Template,
     TemplateArgs,
+    content = AnyContent,
+    categories = [Metadata, Flow, Phrasing, ScriptSupporting, NonInteractive],
     r#""#,
     r#"`<template>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/template)"#,
     r#""#,
@@ -2545,6 +3304,8 @@ tag!(
     textarea,
     Textarea,
     TextareaArgs,
+    content = TextContent,
+    categories = [Flow, Phrasing, Interactive],
     r#""#,
     r#"`<textarea>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/textarea)"#,
     r#""#,
@@ -2562,6 +3323,8 @@ tag!(
     tfoot,
     Tfoot,
     TfootArgs,
+    content = TableSectionContent,
+    categories = [],
     r#""#,
     r#"`<tfoot>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/tfoot)"#,
     r#""#,
@@ -2580,6 +3343,8 @@ tag!(
     th,
     Th,
     ThArgs,
+    content = FlowContent,
+    categories = [],
     r#""#,
     r#"`<th>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/th)"#,
     r#""#,
@@ -2599,6 +3364,8 @@ tag!(
     thead,
     Thead,
     TheadArgs,
+    content = TableSectionContent,
+    categories = [],
     r#""#,
     r#"`<thead>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/thead)"#,
     r#""#,
@@ -2616,6 +3383,8 @@ tag!(
     time,
     Time,
     TimeArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<time>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/time)"#,
     r#""#,
@@ -2633,6 +3402,8 @@ tag!(
     title,
     Title,
     TitleArgs,
+    content = TextContent,
+    categories = [Metadata],
     r#""#,
     r#"`<title>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/title)"#,
     r#""#,
@@ -2650,6 +3421,8 @@ tag!(
     tr,
     Tr,
     TrArgs,
+    content = RowContent,
+    categories = [],
     r#""#,
     r#"`<tr>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/tr)"#,
     r#""#,
@@ -2664,9 +3437,10 @@ tag!(
     r#"- [dom API](https://developer.mozilla.org/zh-CN/docs/Web/API/HTMLTableRowElement)"#
 );
 tag!(
-    track,
+    void track,
     Track,
     TrackArgs,
+    categories = [],
     r#""#,
     r#"`<track>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/track)"#,
     r#""#,
@@ -2684,6 +3458,8 @@ tag!(
     u,
     U,
     UArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<u>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/u)"#,
     r#""#,
@@ -2701,6 +3477,8 @@ tag!(
     ul,
     Ul,
     UlArgs,
+    content = ListContent,
+    categories = [Flow, NonInteractive],
     r#""#,
     r#"`<ul>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/ul)"#,
     r#""#,
@@ -2718,6 +3496,8 @@ tag!(
     var,
     Var,
     VarArgs,
+    content = PhrasingContent,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<var>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/var)"#,
     r#""#,
@@ -2735,6 +3515,8 @@ tag!(
     video,
     Video,
     VideoArgs,
+    content = MediaContent,
+    categories = [Flow, Phrasing, Embedded, Interactive],
     r#""#,
     r#"`<video>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/video)"#,
     r#""#,
@@ -2749,9 +3531,10 @@ tag!(
     r#"- [dom API]()"#
 );
 tag!(
-    wbr,
+    void wbr,
     Wbr,
     WbrArgs,
+    categories = [Flow, Phrasing, NonInteractive],
     r#""#,
     r#"`<wbr>` [doc](https://developer.mozilla.org/zh-CN/docs/Web/HTML/Element/wbr)"#,
     r#""#,
@@ -0,0 +1,712 @@
+//! walk, query and modify a tree after it is built, e.g. in middleware post-processing pages
+//!
+//! built in tags deref to [`UnitTag`], children are reached through [`Tag::as_element`]
+//! and [`Tag::as_element_mut`], and downcast with [`downcast_ref`](trait.Tag.html#method.downcast_ref)
+//!
+//! ```
+//! use rtml::tags::*;
+//!
+//! let mut page = body((
+//!     script("run()"),
+//!     ul((li("home"), li("about").class("active"))),
+//!     img(()).src("/static/logo.png").alt("logo"),
+//! ));
+//! page.select_mut("script", |s| s.set_attr("nonce", "r4nd0m"));
+//! page.select_mut("img[src^='/static/']", |img| {
+//!     let src = img.get_attr("src").unwrap().replace("/static/", "https://cdn.example.com/");
+//!     img.set_attr("src", src);
+//! });
+//! let mut count = 0;
+//! page.select_mut("ul > li", |li| {
+//!     li.set_attr("id", format!("item-{}", count));
+//!     count += 1;
+//! });
+//!
+//! assert_eq!(page.select("ul > li.active").len(), 1);
+//! assert_eq!(page.select("li")[1].get_attr("id"), Some("item-1"));
+//! let html = page.to_string();
+//! assert!(html.contains(r#"<script nonce="r4nd0m">"#));
+//! assert!(html.contains(r#"src="https://cdn.example.com/logo.png""#));
+//! ```
+
+use std::{any::Any, fmt::Display};
+
+use crate::{
+    attrs::{is_valid_attr_name, AttrValue},
+    tags::UnitTag,
+    InnerChildren, Tag,
+};
+
+impl dyn Tag {
+    /// the tag as `T`, e.g. a [`Div`](crate::tags::Div) or a `String`, wrappers such as `Option`
+    /// are seen through
+    ///
+    /// ```
+    /// use rtml::tags::*;
+    ///
+    /// let mut list = ul((li("a"), Some(li("b"))));
+    /// assert!(list.children()[1].is::<Li>());
+    /// let first = list.children_mut()[0].downcast_mut::<Li>().unwrap();
+    /// first.set_attr("class", "first");
+    /// assert!(list.to_string().contains(r#"<li class="first">a</li>"#));
+    /// ```
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.as_any()?.downcast_ref()
+    }
+
+    pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.as_any_mut()?.downcast_mut()
+    }
+
+    /// whether the tag is a `T`
+    pub fn is<T: Any>(&self) -> bool {
+        self.downcast_ref::<T>().is_some()
+    }
+}
+
+impl UnitTag {
+    /// value of attribute `name`, names are case insensitive
+    pub fn get_attr(&self, name: &str) -> Option<&str> {
+        self.props
+            .0
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// set attribute `name`, `None` removes it, `bool` is written as [`AttrValue::into_attr_named`] does,
+    /// names are case insensitive and an attribute already set keeps its place and spelling
+    ///
+    /// ```
+    /// use rtml::{prop, tags::*};
+    ///
+    /// let mut svg = x(("svg", ())).props(prop! { "viewBox" = "0 0 1 1" });
+    /// svg.set_attr("viewbox", "0 0 2 2");
+    /// assert_eq!(svg.get_attr("viewBox"), Some("0 0 2 2"));
+    /// assert_eq!(svg.remove_attr("VIEWBOX").as_deref(), Some("0 0 2 2"));
+    /// assert!(svg.props.0.is_empty());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// if `name` can not be written as an attribute name, check it with [`is_valid_attr_name`]
+    pub fn set_attr<V: AttrValue>(&mut self, name: &str, value: V) {
+        assert!(
+            is_valid_attr_name(name),
+            "invalid attribute name {:?}",
+            name
+        );
+        let key = self.attr_key(name);
+        self.props.set(&key, value.into_attr_named(&key));
+    }
+
+    /// remove attribute `name`, names are case insensitive
+    pub fn remove_attr(&mut self, name: &str) -> Option<String> {
+        let key = self.attr_key(name);
+        self.props.0.remove(&key)
+    }
+
+    /// the name attribute `name` is set under, `name` itself if it is not set
+    fn attr_key(&self, name: &str) -> String {
+        self.props
+            .0
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map_or_else(|| name.to_string(), |(k, _)| k.clone())
+    }
+
+    pub fn has_class(&self, class: &str) -> bool {
+        self.get_attr("class")
+            .is_some_and(|classes| classes.split_ascii_whitespace().any(|c| c == class))
+    }
+
+    pub fn children(&self) -> &InnerChildren {
+        &self.children
+    }
+
+    pub fn children_mut(&mut self) -> &mut InnerChildren {
+        &mut self.children
+    }
+
+    /// add a child at the end
+    pub fn push_child<T: Tag + 'static>(&mut self, child: T) {
+        self.children.push(Box::new(child));
+    }
+
+    /// children which are elements, text and other nodes are skipped
+    pub fn child_elements(&self) -> impl Iterator<Item = &UnitTag> {
+        self.children.iter().filter_map(|c| c.as_element())
+    }
+
+    pub fn child_elements_mut(&mut self) -> impl Iterator<Item = &mut UnitTag> {
+        self.children.iter_mut().filter_map(|c| c.as_element_mut())
+    }
+
+    /// this element and all elements below it, in document order
+    pub fn descendants(&self) -> Vec<&UnitTag> {
+        let mut elements = vec![];
+        self.walk(&mut |el| elements.push(el));
+        elements
+    }
+
+    /// visit this element and all elements below it, in document order
+    pub fn walk<'a>(&'a self, f: &mut dyn FnMut(&'a UnitTag)) {
+        f(self);
+        for child in self.child_elements() {
+            child.walk(f);
+        }
+    }
+
+    /// visit this element and all elements below it, in document order, parents before their children
+    pub fn walk_mut(&mut self, f: &mut dyn FnMut(&mut UnitTag)) {
+        f(self);
+        for child in self.child_elements_mut() {
+            child.walk_mut(f);
+        }
+    }
+
+    /// elements matching a css selector, this element included, in document order
+    ///
+    /// # Panics
+    ///
+    /// if the selector is not valid, use [`Selector::parse`] to handle the error
+    pub fn select(&self, selector: &str) -> Vec<&UnitTag> {
+        parse_or_panic(selector).select(self)
+    }
+
+    /// call `f` on each element matching a css selector, this element included, in document order
+    ///
+    /// # Panics
+    ///
+    /// if the selector is not valid, use [`Selector::parse`] to handle the error
+    pub fn select_mut(&mut self, selector: &str, f: impl FnMut(&mut UnitTag)) {
+        parse_or_panic(selector).select_mut(self, f)
+    }
+}
+
+fn parse_or_panic(selector: &str) -> Selector {
+    match Selector::parse(selector) {
+        Ok(selector) => selector,
+        Err(e) => panic!("{}", e),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorError {
+    pub selector: String,
+    pub message: String,
+}
+
+impl Display for SelectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid selector `{}`: {}", self.selector, self.message)
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+    /// `a b`
+    Descendant,
+    /// `a > b`
+    Child,
+    /// `a + b`
+    NextSibling,
+    /// `a ~ b`
+    SubsequentSibling,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AttrOp {
+    Exists,
+    /// `=`
+    Equals(String),
+    /// `~=`
+    Includes(String),
+    /// `|=`
+    DashMatch(String),
+    /// `^=`
+    Prefix(String),
+    /// `$=`
+    Suffix(String),
+    /// `*=`
+    Substring(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Pseudo {
+    FirstChild,
+    LastChild,
+    OnlyChild,
+    /// `:nth-child(n)`, starts from 1
+    NthChild(usize),
+    Not(Box<Compound>),
+}
+
+/// `tag#id.class[attr]:pseudo`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Compound {
+    tag: Option<String>,
+    ids: Vec<String>,
+    classes: Vec<String>,
+    attrs: Vec<(String, AttrOp)>,
+    pseudos: Vec<Pseudo>,
+}
+
+/// where an element is among its sibling elements, for structural pseudo classes
+#[derive(Clone, Copy)]
+struct Position<'t> {
+    el: &'t UnitTag,
+    index: usize,
+    /// number of sibling elements, the element included
+    count: usize,
+}
+
+/// for each complex selector, whether each of its compounds matches an element
+/// with the compounds before it matching the element's surroundings
+#[derive(Clone)]
+struct Matched(Vec<Vec<bool>>);
+
+impl Matched {
+    fn or(&self, other: &Matched) -> Matched {
+        let or = self.0.iter().zip(&other.0);
+        let or = or.map(|(a, b)| a.iter().zip(b).map(|(a, b)| *a || *b).collect());
+        Matched(or.collect())
+    }
+}
+
+/// what matched around an element, which is all that combinators look at
+struct Surroundings<'m> {
+    parent: &'m Matched,
+    /// any of the ancestors
+    ancestors: &'m Matched,
+    previous: &'m Matched,
+    /// any of the previous siblings
+    before: &'m Matched,
+}
+
+impl Compound {
+    fn matches(&self, pos: &Position) -> bool {
+        let el = pos.el;
+        if let Some(tag) = &self.tag {
+            if !tag.eq_ignore_ascii_case(&el.tag) {
+                return false;
+            }
+        }
+        if !self.ids.iter().all(|id| el.get_attr("id") == Some(id)) {
+            return false;
+        }
+        if !self.classes.iter().all(|c| el.has_class(c)) {
+            return false;
+        }
+        let attrs_match = self.attrs.iter().all(|(name, op)| {
+            let value = match el.get_attr(name) {
+                Some(value) => value,
+                None => return false,
+            };
+            match op {
+                AttrOp::Exists => true,
+                AttrOp::Equals(v) => value == v,
+                AttrOp::Includes(v) => value.split_ascii_whitespace().any(|w| w == v),
+                AttrOp::DashMatch(v) => {
+                    value == v || value.strip_prefix(v.as_str()).is_some_and(|r| r.starts_with('-'))
+                }
+                AttrOp::Prefix(v) => !v.is_empty() && value.starts_with(v.as_str()),
+                AttrOp::Suffix(v) => !v.is_empty() && value.ends_with(v.as_str()),
+                AttrOp::Substring(v) => !v.is_empty() && value.contains(v.as_str()),
+            }
+        });
+        if !attrs_match {
+            return false;
+        }
+        self.pseudos.iter().all(|pseudo| match pseudo {
+            Pseudo::FirstChild => pos.index == 0,
+            Pseudo::LastChild => pos.index + 1 == pos.count,
+            Pseudo::OnlyChild => pos.count == 1,
+            Pseudo::NthChild(n) => pos.index + 1 == *n,
+            Pseudo::Not(inner) => !inner.matches(pos),
+        })
+    }
+}
+
+/// compounds joined by combinators, `combinators[i]` is between `compounds[i]` and `compounds[i + 1]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Complex {
+    compounds: Vec<Compound>,
+    combinators: Vec<Combinator>,
+}
+
+impl Complex {
+    /// which compounds match the element at `pos`, `c` is the index of this complex selector
+    fn matched(&self, c: usize, pos: &Position, around: &Surroundings) -> Vec<bool> {
+        let mut matched = Vec::with_capacity(self.compounds.len());
+        for (i, compound) in self.compounds.iter().enumerate() {
+            let before = i == 0
+                || match self.combinators[i - 1] {
+                    Combinator::Child => around.parent.0[c][i - 1],
+                    Combinator::Descendant => around.ancestors.0[c][i - 1],
+                    Combinator::NextSibling => around.previous.0[c][i - 1],
+                    Combinator::SubsequentSibling => around.before.0[c][i - 1],
+                };
+            matched.push(before && compound.matches(pos));
+        }
+        matched
+    }
+}
+
+/// a parsed css selector list, supporting type, universal, id, class and attribute selectors,
+/// `:first-child`, `:last-child`, `:only-child`, `:nth-child(n)` and `:not(..)`,
+/// and the descendant, `>`, `+` and `~` combinators
+///
+/// ```
+/// use rtml::{tags::*, tree::Selector};
+///
+/// let list = ul((li("a"), li("b").class("active"), li("c")));
+/// let selector = Selector::parse("li.active + li, li:first-child").unwrap();
+/// let texts: Vec<_> = selector.select(&list).iter().map(|li| li.to_string()).collect();
+/// assert_eq!(texts, ["<li>a</li>\n", "<li>c</li>\n"]);
+/// assert!(Selector::parse("li >").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector(Vec<Complex>);
+
+impl Selector {
+    pub fn parse(selector: &str) -> Result<Self, SelectorError> {
+        let mut parser = Parser {
+            chars: selector.chars().collect(),
+            pos: 0,
+        };
+        parser.selector_list().map_err(|message| SelectorError {
+            selector: selector.to_string(),
+            message,
+        })
+    }
+
+    /// elements under `root` matching the selector, `root` included, in document order
+    pub fn select<'t>(&self, root: &'t UnitTag) -> Vec<&'t UnitTag> {
+        let mut matched = vec![];
+        let none = self.none();
+        let around = Surroundings {
+            parent: &none,
+            ancestors: &none,
+            previous: &none,
+            before: &none,
+        };
+        let pos = Position {
+            el: root,
+            index: 0,
+            count: 1,
+        };
+        self.collect(pos, &around, &mut |el| matched.push(el));
+        matched
+    }
+
+    /// call `f` on elements under `root` matching the selector, `root` included, in document order
+    ///
+    /// each element is matched in the tree as the previous calls left it, so `f` may change
+    /// the children of the element it is given, the new children are visited instead
+    ///
+    /// ```
+    /// use rtml::tags::*;
+    ///
+    /// let mut page = div(div(div("x")));
+    /// page.select_mut("div", |d| d.children_mut().clear());
+    /// assert!(page.children().is_empty());
+    /// ```
+    pub fn select_mut(&self, root: &mut UnitTag, mut f: impl FnMut(&mut UnitTag)) {
+        let none = self.none();
+        let around = Surroundings {
+            parent: &none,
+            ancestors: &none,
+            previous: &none,
+            before: &none,
+        };
+        self.visit_mut(root, 0, 1, &around, &mut f);
+    }
+
+    /// nothing matched, around the root
+    fn none(&self) -> Matched {
+        let none = self.0.iter().map(|c| vec![false; c.compounds.len()]);
+        Matched(none.collect())
+    }
+
+    fn matched(&self, pos: &Position, around: &Surroundings) -> Matched {
+        let matched = self.0.iter().enumerate();
+        let matched = matched.map(|(c, complex)| complex.matched(c, pos, around));
+        Matched(matched.collect())
+    }
+
+    /// whether the last compound of any complex selector matched
+    fn is_match(matched: &Matched) -> bool {
+        matched.0.iter().any(|m| m.last() == Some(&true))
+    }
+
+    /// call `f` on the elements matching at and below `pos`, returns what matched at `pos`
+    fn collect<'t>(
+        &self,
+        pos: Position<'t>,
+        around: &Surroundings,
+        f: &mut dyn FnMut(&'t UnitTag),
+    ) -> Matched {
+        let matched = self.matched(&pos, around);
+        if Self::is_match(&matched) {
+            f(pos.el);
+        }
+        let ancestors = around.ancestors.or(&matched);
+        let mut previous = self.none();
+        let mut before = self.none();
+        let count = pos.el.child_elements().count();
+        for (index, el) in pos.el.child_elements().enumerate() {
+            let around = Surroundings {
+                parent: &matched,
+                ancestors: &ancestors,
+                previous: &previous,
+                before: &before,
+            };
+            let child = self.collect(Position { el, index, count }, &around, f);
+            before = before.or(&child);
+            previous = child;
+        }
+        matched
+    }
+
+    /// like [`collect`](Self::collect), `el` is matched again after `f` as it may have changed,
+    /// everything matched before it is final, as `f` is only given elements after it
+    fn visit_mut(
+        &self,
+        el: &mut UnitTag,
+        index: usize,
+        count: usize,
+        around: &Surroundings,
+        f: &mut dyn FnMut(&mut UnitTag),
+    ) -> Matched {
+        let mut matched = self.matched(&Position { el, index, count }, around);
+        if Self::is_match(&matched) {
+            f(el);
+            matched = self.matched(&Position { el, index, count }, around);
+        }
+        let ancestors = around.ancestors.or(&matched);
+        let mut previous = self.none();
+        let mut before = self.none();
+        let count = el.child_elements().count();
+        for (index, child) in el.child_elements_mut().enumerate() {
+            let around = Surroundings {
+                parent: &matched,
+                ancestors: &ancestors,
+                previous: &previous,
+                before: &before,
+            };
+            let child = self.visit_mut(child, index, count, &around, f);
+            before = before.or(&child);
+            previous = child;
+        }
+        matched
+    }
+}
+
+impl std::str::FromStr for Selector {
+    type Err = SelectorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.pos;
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+        self.pos > start
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(format!("expect `{}` at {}", c, self.pos))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, String> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_alphanumeric() || c == '-' || c == '_' || !c.is_ascii())
+        {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Err(format!("expect a name at {}", self.pos));
+        }
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    fn selector_list(&mut self) -> Result<Selector, String> {
+        let mut list = vec![];
+        loop {
+            self.skip_whitespace();
+            list.push(self.complex()?);
+            if !self.eat(',') {
+                break;
+            }
+        }
+        if self.pos < self.chars.len() {
+            return Err(format!("unexpected `{}` at {}", self.chars[self.pos], self.pos));
+        }
+        Ok(Selector(list))
+    }
+
+    fn complex(&mut self) -> Result<Complex, String> {
+        let mut compounds = vec![self.compound()?];
+        let mut combinators = vec![];
+        loop {
+            let spaced = self.skip_whitespace();
+            let combinator = match self.peek() {
+                Some('>') => Combinator::Child,
+                Some('+') => Combinator::NextSibling,
+                Some('~') => Combinator::SubsequentSibling,
+                Some(',') | None => break,
+                Some(_) if spaced => Combinator::Descendant,
+                Some(c) => return Err(format!("unexpected `{}` at {}", c, self.pos)),
+            };
+            if combinator != Combinator::Descendant {
+                self.pos += 1;
+                self.skip_whitespace();
+            }
+            combinators.push(combinator);
+            compounds.push(self.compound()?);
+        }
+        Ok(Complex {
+            compounds,
+            combinators,
+        })
+    }
+
+    fn compound(&mut self) -> Result<Compound, String> {
+        let mut compound = Compound::default();
+        let start = self.pos;
+        if !self.eat('*') && self.peek().is_some_and(|c| c.is_alphabetic()) {
+            compound.tag = Some(self.ident()?);
+        }
+        loop {
+            match self.peek() {
+                Some('#') => {
+                    self.pos += 1;
+                    compound.ids.push(self.ident()?);
+                }
+                Some('.') => {
+                    self.pos += 1;
+                    compound.classes.push(self.ident()?);
+                }
+                Some('[') => {
+                    self.pos += 1;
+                    compound.attrs.push(self.attr()?);
+                }
+                Some(':') => {
+                    self.pos += 1;
+                    compound.pseudos.push(self.pseudo()?);
+                }
+                _ => break,
+            }
+        }
+        if self.pos == start {
+            return Err(format!("expect a selector at {}", self.pos));
+        }
+        Ok(compound)
+    }
+
+    fn attr(&mut self) -> Result<(String, AttrOp), String> {
+        self.skip_whitespace();
+        let name = self.ident()?;
+        self.skip_whitespace();
+        if self.eat(']') {
+            return Ok((name, AttrOp::Exists));
+        }
+        let op = match self.peek() {
+            Some('=') => None,
+            Some(c @ ('~' | '|' | '^' | '$' | '*')) => {
+                self.pos += 1;
+                Some(c)
+            }
+            _ => return Err(format!("expect `]` or an operator at {}", self.pos)),
+        };
+        self.expect('=')?;
+        self.skip_whitespace();
+        let value = match self.peek() {
+            Some(quote @ ('"' | '\'')) => {
+                self.pos += 1;
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c != quote) {
+                    self.pos += 1;
+                }
+                let value = self.chars[start..self.pos].iter().collect();
+                self.expect(quote)?;
+                value
+            }
+            _ => self.ident()?,
+        };
+        self.skip_whitespace();
+        self.expect(']')?;
+        let op = match op {
+            None => AttrOp::Equals(value),
+            Some('~') => AttrOp::Includes(value),
+            Some('|') => AttrOp::DashMatch(value),
+            Some('^') => AttrOp::Prefix(value),
+            Some('$') => AttrOp::Suffix(value),
+            _ => AttrOp::Substring(value),
+        };
+        Ok((name, op))
+    }
+
+    fn pseudo(&mut self) -> Result<Pseudo, String> {
+        let name = self.ident()?.to_ascii_lowercase();
+        match name.as_str() {
+            "first-child" => Ok(Pseudo::FirstChild),
+            "last-child" => Ok(Pseudo::LastChild),
+            "only-child" => Ok(Pseudo::OnlyChild),
+            "nth-child" => {
+                self.expect('(')?;
+                self.skip_whitespace();
+                let n = self.ident()?;
+                let n = n
+                    .parse()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| format!("expect a positive number, found `{}`", n))?;
+                self.skip_whitespace();
+                self.expect(')')?;
+                Ok(Pseudo::NthChild(n))
+            }
+            "not" => {
+                self.expect('(')?;
+                self.skip_whitespace();
+                let inner = self.compound()?;
+                self.skip_whitespace();
+                self.expect(')')?;
+                Ok(Pseudo::Not(Box::new(inner)))
+            }
+            _ => Err(format!("unsupported pseudo class `:{}`", name)),
+        }
+    }
+}
@@ -0,0 +1,509 @@
+//! runtime conformance checks, for what the type system can not see: trees built from
+//! [`Children`](crate::Children) or [`x`](crate::tags::x), attributes, ids and elements
+//! which may only appear once in a page
+//!
+//! checking walks the whole tree, so it is meant for debug builds and tests
+//!
+//! ```
+//! use rtml::{prop, tags::*, validate::validate};
+//!
+//! let page = div((
+//!     img(prop! { id = "logo", src = "logo.png" }),
+//!     p(prop! { id = "logo", colour = "red" }),
+//! ));
+//! let problems: Vec<_> = validate(&page).iter().map(ToString::to_string).collect();
+//! assert_eq!(
+//!     problems,
+//!     [
+//!         "div > img#logo: `img` is missing required attribute `alt`",
+//!         "div > p#logo: duplicate id `logo`",
+//!         "div > p#logo: unknown attribute `colour` on `p`",
+//!     ]
+//! );
+//! ```
+
+use std::{collections::HashSet, fmt::Display};
+
+use crate::{
+    tags::{is_void, TagFormatter, UnitTag},
+    Tag,
+};
+
+/// a problem found by [`validate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// css like path of the element, e.g. `body > ul > li`
+    pub path: String,
+    pub kind: DiagnosticKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// `child` is not permitted by the content model of `parent`
+    InvalidChild {
+        parent: String,
+        child: String,
+    },
+    MissingAttribute {
+        tag: String,
+        attr: String,
+    },
+    /// the id is already used by an element before
+    DuplicateId(String),
+    UnknownAttribute {
+        tag: String,
+        attr: String,
+    },
+    /// element which may only appear once in a page, e.g. `title`, `main`
+    Repeated(String),
+}
+
+impl Display for DiagnosticKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidChild { parent, child } => {
+                write!(f, "`{}` is not permitted in `{}`", child, parent)
+            }
+            Self::MissingAttribute { tag, attr } => {
+                write!(f, "`{}` is missing required attribute `{}`", tag, attr)
+            }
+            Self::DuplicateId(id) => write!(f, "duplicate id `{}`", id),
+            Self::UnknownAttribute { tag, attr } => {
+                write!(f, "unknown attribute `{}` on `{}`", attr, tag)
+            }
+            Self::Repeated(tag) => write!(f, "`{}` should only appear once", tag),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.kind)
+    }
+}
+
+/// attributes every element accepts, besides `data-*` and `aria-*`
+pub const GLOBAL_ATTRIBUTES: [&str; 31] = [
+    "accesskey",
+    "autocapitalize",
+    "autofocus",
+    "class",
+    "contenteditable",
+    "dir",
+    "draggable",
+    "enterkeyhint",
+    "hidden",
+    "id",
+    "inert",
+    "inputmode",
+    "is",
+    "itemid",
+    "itemprop",
+    "itemref",
+    "itemscope",
+    "itemtype",
+    "lang",
+    "nonce",
+    "part",
+    "popover",
+    "role",
+    "slot",
+    "spellcheck",
+    "style",
+    "tabindex",
+    "title",
+    "translate",
+    "writingsuggestions",
+    "xmlns",
+];
+
+/// elements which may only appear once in a page
+pub const ONCE_ELEMENTS: [&str; 6] = ["html", "head", "body", "title", "main", "base"];
+
+/// attributes an element can not go without
+pub fn required_attributes(tag: &str) -> &'static [&'static str] {
+    match tag {
+        "img" => &["src", "alt"],
+        "html" => &["lang"],
+        "link" => &["rel", "href"],
+        "optgroup" => &["label"],
+        "track" => &["src"],
+        _ => &[],
+    }
+}
+
+/// attributes an element accepts besides the global ones, `None` for elements unknown to html,
+/// whose attributes are not checked
+pub fn element_attributes(tag: &str) -> Option<&'static [&'static str]> {
+    let attrs: &[&str] = match tag {
+        "a" => &[
+            "href",
+            "target",
+            "download",
+            "ping",
+            "rel",
+            "hreflang",
+            "type",
+            "referrerpolicy",
+        ],
+        "area" => &[
+            "alt",
+            "coords",
+            "shape",
+            "href",
+            "target",
+            "download",
+            "ping",
+            "rel",
+            "referrerpolicy",
+        ],
+        "audio" | "video" => &[
+            "src",
+            "crossorigin",
+            "preload",
+            "autoplay",
+            "loop",
+            "muted",
+            "controls",
+            "controlslist",
+            "disableremoteplayback",
+            "poster",
+            "playsinline",
+            "width",
+            "height",
+        ],
+        "base" => &["href", "target"],
+        "blockquote" | "q" => &["cite"],
+        "del" | "ins" => &["cite", "datetime"],
+        "button" => &[
+            "disabled",
+            "form",
+            "formaction",
+            "formenctype",
+            "formmethod",
+            "formnovalidate",
+            "formtarget",
+            "name",
+            "popovertarget",
+            "popovertargetaction",
+            "type",
+            "value",
+        ],
+        "canvas" => &["width", "height"],
+        "col" | "colgroup" => &["span"],
+        "data" => &["value"],
+        "details" => &["open", "name"],
+        "dialog" => &["open"],
+        "embed" => &["src", "type", "width", "height"],
+        "fieldset" => &["disabled", "form", "name"],
+        "form" => &[
+            "accept-charset",
+            "action",
+            "autocomplete",
+            "enctype",
+            "method",
+            "name",
+            "novalidate",
+            "target",
+            "rel",
+        ],
+        "iframe" => &[
+            "src",
+            "srcdoc",
+            "name",
+            "sandbox",
+            "allow",
+            "allowfullscreen",
+            "width",
+            "height",
+            "referrerpolicy",
+            "loading",
+        ],
+        "img" => &[
+            "alt",
+            "src",
+            "srcset",
+            "sizes",
+            "crossorigin",
+            "usemap",
+            "ismap",
+            "width",
+            "height",
+            "referrerpolicy",
+            "decoding",
+            "loading",
+            "fetchpriority",
+        ],
+        "input" => &[
+            "accept",
+            "alt",
+            "autocomplete",
+            "checked",
+            "dirname",
+            "disabled",
+            "form",
+            "formaction",
+            "formenctype",
+            "formmethod",
+            "formnovalidate",
+            "formtarget",
+            "height",
+            "list",
+            "max",
+            "maxlength",
+            "min",
+            "minlength",
+            "multiple",
+            "name",
+            "pattern",
+            "placeholder",
+            "popovertarget",
+            "popovertargetaction",
+            "readonly",
+            "required",
+            "size",
+            "src",
+            "step",
+            "type",
+            "value",
+            "width",
+        ],
+        "label" => &["for"],
+        "li" => &["value"],
+        "link" => &[
+            "href",
+            "crossorigin",
+            "rel",
+            "media",
+            "integrity",
+            "hreflang",
+            "type",
+            "referrerpolicy",
+            "sizes",
+            "imagesrcset",
+            "imagesizes",
+            "as",
+            "blocking",
+            "color",
+            "disabled",
+            "fetchpriority",
+        ],
+        "map" => &["name"],
+        "meta" => &["name", "http-equiv", "content", "charset", "media"],
+        "meter" => &["value", "min", "max", "low", "high", "optimum"],
+        "object" => &["data", "type", "name", "form", "width", "height"],
+        "ol" => &["reversed", "start", "type"],
+        "optgroup" => &["disabled", "label"],
+        "option" => &["disabled", "label", "selected", "value"],
+        "output" => &["for", "form", "name"],
+        "progress" => &["value", "max"],
+        "script" => &[
+            "src",
+            "type",
+            "nomodule",
+            "async",
+            "defer",
+            "crossorigin",
+            "integrity",
+            "referrerpolicy",
+            "blocking",
+            "fetchpriority",
+        ],
+        "select" => &[
+            "autocomplete",
+            "disabled",
+            "form",
+            "multiple",
+            "name",
+            "required",
+            "size",
+        ],
+        "slot" => &["name"],
+        "source" => &["type", "media", "src", "srcset", "sizes", "width", "height"],
+        "style" => &["media", "blocking"],
+        "td" => &["colspan", "rowspan", "headers"],
+        "th" => &["colspan", "rowspan", "headers", "scope", "abbr"],
+        "template" => &[
+            "shadowrootmode",
+            "shadowrootdelegatesfocus",
+            "shadowrootclonable",
+        ],
+        "textarea" => &[
+            "autocomplete",
+            "cols",
+            "dirname",
+            "disabled",
+            "form",
+            "maxlength",
+            "minlength",
+            "name",
+            "placeholder",
+            "readonly",
+            "required",
+            "rows",
+            "wrap",
+        ],
+        "time" => &["datetime"],
+        "track" => &["default", "kind", "label", "src", "srclang"],
+        "html" => &["manifest"],
+        "abbr" | "address" | "article" | "aside" | "b" | "bdi" | "bdo" | "body" | "br"
+        | "caption" | "cite" | "code" | "datalist" | "dd" | "dfn" | "div" | "dl" | "dt" | "em"
+        | "figcaption" | "figure" | "footer" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "head"
+        | "header" | "hgroup" | "hr" | "i" | "kbd" | "legend" | "main" | "mark" | "menu"
+        | "nav" | "noscript" | "p" | "picture" | "pre" | "rp" | "rt" | "ruby" | "s" | "samp"
+        | "search" | "section" | "small" | "span" | "strong" | "sub" | "summary" | "sup"
+        | "table" | "tbody" | "tfoot" | "thead" | "title" | "tr" | "u" | "ul" | "var" | "wbr" => {
+            &[]
+        }
+        _ => return None,
+    };
+    Some(attrs)
+}
+
+/// comments and whitespace text, such as the indentation of parsed html, are allowed anywhere
+fn is_inter_element(child: &dyn Tag) -> bool {
+    match child.name() {
+        "#comment" => true,
+        // text can be any type, such as `&str` or `char`, so it is rendered to be checked
+        "#text" => {
+            let mut text = String::new();
+            TagFormatter::exact().render(child, &mut text).is_ok() && text.trim_ascii().is_empty()
+        }
+        _ => false,
+    }
+}
+
+fn is_known_attribute(tag: &str, attr: &str) -> bool {
+    let attr = attr.to_ascii_lowercase();
+    // `on*` are event handlers, kept as props by the parser
+    if attr.starts_with("data-") || attr.starts_with("aria-") || attr.starts_with("on") {
+        return true;
+    }
+    if GLOBAL_ATTRIBUTES.contains(&attr.as_str()) {
+        return true;
+    }
+    match element_attributes(tag) {
+        Some(attrs) => attrs.contains(&attr.as_str()),
+        None => true,
+    }
+}
+
+/// walks trees and collects diagnostics, state is shared between the trees it visits,
+/// so a page assembled from parts can be checked as a whole
+#[derive(Debug, Default)]
+pub struct Validator {
+    ids: HashSet<String>,
+    seen: HashSet<&'static str>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Validator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// check `tag` and its descendants, `parent` is the path it is under, empty for a root
+    pub fn visit(&mut self, tag: &dyn Tag, parent: &str) {
+        let el = match tag.as_element() {
+            Some(el) => el,
+            None => return,
+        };
+        let path = match el.props.0.get("id") {
+            Some(id) if parent.is_empty() => format!("{}#{}", el.tag, id),
+            Some(id) => format!("{} > {}#{}", parent, el.tag, id),
+            None if parent.is_empty() => el.tag.to_string(),
+            None => format!("{} > {}", parent, el.tag),
+        };
+        self.check_element(el, &path);
+        // children of void elements, only possible with a hand built `UnitTag`, are not rendered
+        let void = is_void(&el.tag);
+        for child in el.children.iter() {
+            let name = child.name();
+            let permitted =
+                !void && (is_inter_element(child.as_ref()) || tag.permits(child.as_ref()));
+            if !name.is_empty() && !permitted {
+                let kind = DiagnosticKind::InvalidChild {
+                    parent: el.tag.to_string(),
+                    child: name.to_string(),
+                };
+                self.report(&path, kind);
+            }
+            self.visit(child.as_ref(), &path);
+        }
+    }
+
+    fn check_element(&mut self, el: &UnitTag, path: &str) {
+        let tag = &*el.tag;
+        if let Some(once) = ONCE_ELEMENTS.iter().find(|once| **once == tag) {
+            self.once(path, once);
+        }
+        for attr in required_attributes(tag) {
+            if !el.props.0.contains_key(attr) {
+                self.missing(path, tag, attr);
+            }
+        }
+        if let Some(id) = el.props.0.get("id") {
+            if !self.ids.insert(id.clone()) {
+                self.report(path, DiagnosticKind::DuplicateId(id.clone()));
+            }
+        }
+        // custom elements define their own attributes
+        if tag.contains('-') {
+            return;
+        }
+        for attr in el.props.0.keys() {
+            if !is_known_attribute(tag, attr) {
+                let kind = DiagnosticKind::UnknownAttribute {
+                    tag: tag.to_string(),
+                    attr: attr.clone(),
+                };
+                self.report(path, kind);
+            }
+        }
+    }
+
+    /// record that `tag` is missing `attr`, for elements checked without a [`UnitTag`]
+    pub fn missing(&mut self, path: &str, tag: &str, attr: &str) {
+        let kind = DiagnosticKind::MissingAttribute {
+            tag: tag.to_string(),
+            attr: attr.to_string(),
+        };
+        self.report(path, kind);
+    }
+
+    /// record that an element which may only appear once is seen, for elements checked without a [`UnitTag`]
+    pub fn once(&mut self, path: &str, tag: &'static str) {
+        if !self.seen.insert(tag) {
+            self.report(path, DiagnosticKind::Repeated(tag.to_string()));
+        }
+    }
+
+    fn report(&mut self, path: &str, kind: DiagnosticKind) {
+        self.diagnostics.push(Diagnostic {
+            path: path.to_string(),
+            kind,
+        });
+    }
+}
+
+/// check a tree, see [`Document::validate`](crate::Document::validate) for complete pages
+pub fn validate(tag: &dyn Tag) -> Vec<Diagnostic> {
+    let mut validator = Validator::new();
+    validator.visit(tag, "");
+    validator.diagnostics
+}
+
+/// panic with every diagnostic if the list is not empty
+///
+/// ```should_panic
+/// use rtml::{tags::*, validate::{assert_valid, validate}};
+///
+/// let list = ul(x(("div", "not a list item")));
+/// assert_valid(&validate(&list));
+/// ```
+pub fn assert_valid(diagnostics: &[Diagnostic]) {
+    if !diagnostics.is_empty() {
+        let lines: Vec<_> = diagnostics.iter().map(ToString::to_string).collect();
+        panic!("invalid html:\n{}", lines.join("\n"));
+    }
+}
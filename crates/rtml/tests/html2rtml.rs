@@ -0,0 +1,78 @@
+//! html2rtml output is checked against the generated files next to the inputs,
+//! which are compiled as modules here, so the generated code is known to build.
+//! they are kept as generated, not formatted by rustfmt
+
+use std::process::Command;
+
+#[rustfmt::skip]
+#[path = "html2rtml/card.rs"]
+mod card;
+#[rustfmt::skip]
+#[path = "html2rtml/custom_element.rs"]
+mod custom_element;
+#[rustfmt::skip]
+#[path = "html2rtml/long_list.rs"]
+mod long_list;
+
+/// stdout and stderr of html2rtml run on `tests/html2rtml/{name}.html`
+fn convert(name: &str) -> (String, String) {
+    let input = format!(
+        "{}/tests/html2rtml/{}.html",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    );
+    let output = Command::new(env!("CARGO_BIN_EXE_html2rtml"))
+        .arg(input)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    (
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+/// path of the code html2rtml is expected to generate for `tests/html2rtml/{name}.html`
+fn expected(name: &str) -> String {
+    format!("{}/tests/html2rtml/{}.rs", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn tuple_for_different_tags_array_for_same_tags() {
+    let (out, err) = convert("card");
+    assert_eq!(out, std::fs::read_to_string(expected("card")).unwrap());
+    assert!(out.contains("(h2(\"title\"), ul([li(\"a\"), li(\"b\")]), p((\"some \""));
+    assert!(err.is_empty());
+
+    let html = card::card().to_string();
+    assert!(html.contains("<li>a</li>"));
+    assert!(html.contains("<p>some <b>bold</b> text</p>"));
+}
+
+#[test]
+fn vec_for_long_lists() {
+    let (out, err) = convert("long_list");
+    assert_eq!(out, std::fs::read_to_string(expected("long_list")).unwrap());
+    assert!(out.contains("ol(vec!["));
+    assert!(err.is_empty());
+
+    let list = long_list::long_list();
+    assert_eq!(list.select("li").len(), 40);
+}
+
+#[test]
+fn custom_elements_are_built_with_x_and_warned_about_once() {
+    let (out, err) = convert("custom_element");
+    assert_eq!(
+        out,
+        std::fs::read_to_string(expected("custom_element")).unwrap()
+    );
+    assert!(out.contains("x((\"my-card\", prop! { size = \"2\" }, span(\"x\")))"));
+    assert_eq!(
+        err,
+        "warning: <my-card> has no tag function in rtml::tags, it is built with x((\"my-card\", ..))\n"
+    );
+
+    let html = custom_element::custom_element().to_string();
+    assert!(html.contains(r#"<my-card size="2"><span>x</span></my-card>"#));
+}
@@ -0,0 +1,8 @@
+use rtml::{prop, tags::*};
+
+pub fn card() -> Div {
+    div((
+        prop! { class = "card" },
+        (h2("title"), ul([li("a"), li("b")]), p(("some ", b("bold"), " text"))),
+    ))
+}
@@ -0,0 +1,5 @@
+use rtml::{prop, tags::*};
+
+pub fn custom_element() -> Section {
+    section([x(("my-card", prop! { size = "2" }, span("x"))), x(("my-card", ()))])
+}
@@ -0,0 +1,46 @@
+use rtml::tags::*;
+
+pub fn long_list() -> Ol {
+    ol(vec![
+        li("0"),
+        li("1"),
+        li("2"),
+        li("3"),
+        li("4"),
+        li("5"),
+        li("6"),
+        li("7"),
+        li("8"),
+        li("9"),
+        li("10"),
+        li("11"),
+        li("12"),
+        li("13"),
+        li("14"),
+        li("15"),
+        li("16"),
+        li("17"),
+        li("18"),
+        li("19"),
+        li("20"),
+        li("21"),
+        li("22"),
+        li("23"),
+        li("24"),
+        li("25"),
+        li("26"),
+        li("27"),
+        li("28"),
+        li("29"),
+        li("30"),
+        li("31"),
+        li("32"),
+        li("33"),
+        li("34"),
+        li("35"),
+        li("36"),
+        li("37"),
+        li("38"),
+        li("39"),
+    ])
+}
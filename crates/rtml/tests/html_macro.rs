@@ -0,0 +1,9 @@
+use rtml::html;
+
+#[test]
+fn event_names_keep_their_own_on() {
+    let page = html! { <body ononline="sync()" onclick="go()">"x"</body> };
+    let html = page.to_string();
+    assert!(html.contains(r#"ononline="sync()""#));
+    assert!(html.contains(r#"onclick="go()""#));
+}
@@ -0,0 +1,66 @@
+use std::fmt::Display;
+
+use rtml::{prop, tags::*};
+
+struct Id(u32);
+
+impl Display for Id {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "item-{}", self.0)
+    }
+}
+
+#[test]
+fn bool_is_present_or_absent_only_for_boolean_attributes() {
+    let field = input(prop! {
+        disabled = true,
+        required = false,
+        draggable = false,
+        spellcheck = false,
+        aria-hidden = true,
+    });
+    assert_eq!(
+        field.to_string().trim_end(),
+        r#"<input disabled draggable="false" spellcheck="false" aria-hidden="true">"#
+    );
+}
+
+#[test]
+fn optional_and_bare_values() {
+    let tip: Option<&str> = None;
+    let el = span(prop! { title = tip, hidden, data-x });
+    assert_eq!(
+        el.to_string().trim_end(),
+        r#"<span hidden data-x=""></span>"#
+    );
+}
+
+#[test]
+fn display_only_values() {
+    let el = span(prop! { id = Id(3) });
+    assert_eq!(el.to_string().trim_end(), r#"<span id="item-3"></span>"#);
+}
+
+#[test]
+fn set_attr_writes_bool_the_same_way() {
+    let mut el = div(());
+    el.set_attr("draggable", true);
+    el.set_attr("hidden", false);
+    assert_eq!(el.get_attr("draggable"), Some("true"));
+    assert_eq!(el.get_attr("hidden"), None);
+}
+
+#[test]
+fn attr_accepts_names_prop_does_not() {
+    let el = span(()).attr("@click", "go").attr(":class", "c");
+    assert_eq!(
+        el.to_string().trim_end(),
+        r#"<span @click="go" :class="c"></span>"#
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid attribute name")]
+fn attr_rejects_names_breaking_out_of_the_tag() {
+    span(()).attr(r#"x"><script>"#, "");
+}
@@ -0,0 +1,86 @@
+use rtml::{
+    parse::{parse, parse_document},
+    tags::TagFormatter,
+};
+
+fn render(src: &str) -> String {
+    let mut out = String::new();
+    let mut f = TagFormatter::exact();
+    for node in parse(src).0 {
+        f.render(node.as_ref(), &mut out).unwrap();
+    }
+    out
+}
+
+fn round_trip(src: &str) {
+    assert_eq!(render(src), src);
+}
+
+#[test]
+fn elements_and_text() {
+    round_trip("<div><p>a &amp; b &lt;c&gt;</p><ul><li>x</li><li>y</li></ul></div>");
+    round_trip("<p>one <b>two</b> <i>three</i>\n  four</p>");
+    round_trip("<pre>\n\n  code\n</pre>");
+}
+
+#[test]
+fn attributes_keep_order() {
+    round_trip(r#"<button onclick="go()" id="b" style="color: red" class="c">go</button>"#);
+    round_trip(r#"<a href="/" onmouseover="hint()" title="home">home</a>"#);
+}
+
+#[test]
+fn empty_attribute_values() {
+    round_trip(r#"<input value="" disabled>"#);
+    round_trip(r#"<option value="" selected>none</option>"#);
+    round_trip(r#"<div data-empty="" hidden></div>"#);
+}
+
+#[test]
+fn style_text_is_kept() {
+    round_trip(r#"<p style="COLOR:red;margin : 0 ;">a</p>"#);
+    round_trip(r#"<p style="color: red /* note */">a</p>"#);
+}
+
+#[test]
+fn changed_style_and_handlers_replace_attributes() {
+    let mut nodes = parse(r#"<div style="color: red" onclick="go()">a</div>"#);
+    let div = nodes.0[0].as_element_mut().unwrap();
+    div.style = rtml::style! { margin: "0" };
+    div.on.insert("click", "stop()");
+    let mut out = String::new();
+    TagFormatter::exact().render(div, &mut out).unwrap();
+    assert_eq!(out, r#"<div style="margin: 0; " onclick="stop()">a</div>"#);
+}
+
+#[test]
+fn raw_text_elements() {
+    round_trip("<script>if (a < b && c > d) { x = '</p>'; }</script>");
+    round_trip("<style>a > b { content: \"&amp;\"; }</style>");
+    round_trip("<xmp><b>not bold</b> &amp;</xmp>");
+    round_trip("<iframe><p>fallback</p></iframe>");
+    round_trip("<noembed><b>x</b></noembed>");
+    round_trip("<noframes><b>x</b></noframes>");
+}
+
+#[test]
+fn comments() {
+    round_trip("<div><!-- note --><p>a</p></div>");
+}
+
+#[test]
+fn custom_and_foreign_elements() {
+    round_trip(r#"<my-card size="2"><span slot="title">t</span></my-card>"#);
+    round_trip(r#"<svg viewBox="0 0 1 1"><foreignObject><p>a</p></foreignObject></svg>"#);
+}
+
+#[test]
+fn document() {
+    let src = "<!DOCTYPE html><html lang=\"en\"><head><title>t</title></head><body onload=\"init()\"><p>a</p></body></html>";
+    let mut out = String::new();
+    let mut f = TagFormatter::exact();
+    for node in parse_document(src).0 {
+        f.render(node.as_ref(), &mut out).unwrap();
+    }
+    assert_eq!(out, src);
+}
@@ -0,0 +1,30 @@
+use rtml::{on, script::JsFunction, tags::*};
+
+#[test]
+fn handlers_pass_event_and_undefined() {
+    let save = JsFunction::new("save", &["event", "id"], "");
+    assert_eq!(save.call(), "save(event, undefined)");
+    let el = button(on! { click = &save });
+    assert!(el
+        .to_string()
+        .contains(r#"onclick="save(event, undefined)""#));
+}
+
+#[test]
+#[should_panic(expected = "invalid js function or param name")]
+fn function_names_are_identifiers() {
+    JsFunction::new("go()", &[], "");
+}
+
+#[test]
+#[should_panic(expected = "invalid js function or param name \"this\"")]
+fn params_are_not_reserved_words() {
+    JsFunction::new("go", &["this"], "");
+}
+
+#[test]
+#[should_panic(expected = "invalid event name")]
+fn event_names_are_identifiers() {
+    let mut handlers = TagHandler::default();
+    handlers.insert("click\" onload=\"x", "go()");
+}
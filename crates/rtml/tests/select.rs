@@ -0,0 +1,56 @@
+use rtml::tags::*;
+
+fn texts(els: &[&UnitTag]) -> Vec<String> {
+    els.iter().map(|el| el.to_string()).collect()
+}
+
+#[test]
+fn combinators_and_structural_pseudo_classes() {
+    let page = div((
+        ul((li("a"), li("b").class("on"), li("c"), li("d"))),
+        p(span("x")),
+    ));
+    assert_eq!(texts(&page.select("li.on + li")), ["<li>c</li>\n"]);
+    assert_eq!(page.select("li.on ~ li").len(), 2);
+    assert_eq!(texts(&page.select("li:last-child")), ["<li>d</li>\n"]);
+    assert_eq!(page.select("div > li").len(), 0);
+    assert_eq!(page.select("div li").len(), 4);
+    assert_eq!(page.select("div > p > span:only-child").len(), 1);
+    assert_eq!(page.select("ul + p span").len(), 1);
+    assert_eq!(page.select("div:first-child").len(), 1);
+}
+
+#[test]
+fn select_mut_sees_earlier_changes() {
+    let mut list = ul((li("a"), li("b"), li("c")));
+    list.select_mut("li:first-child, li.on + li", |li| {
+        li.set_attr("class", "on")
+    });
+    assert_eq!(list.select("li.on").len(), 3);
+
+    let mut page = div(div(()));
+    page.select_mut("div", |d| {
+        if d.child_elements().next().is_none() {
+            d.push_child(span(()));
+        }
+    });
+    page.select_mut("div div > span", |s| s.set_attr("id", "s"));
+    assert_eq!(page.select("#s").len(), 1);
+}
+
+#[test]
+fn wide_and_deep_trees() {
+    let wide = ul((0..5000).map(|i| li(i.to_string())).collect::<Vec<_>>());
+    assert_eq!(
+        wide.select("li:last-child + li, li ~ li:last-child").len(),
+        1
+    );
+
+    let mut deep = div(());
+    for _ in 0..500 {
+        deep = div(deep);
+    }
+    let mut count = 0;
+    deep.select_mut("div div", |_| count += 1);
+    assert_eq!(count, 500);
+}
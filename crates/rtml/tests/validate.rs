@@ -0,0 +1,26 @@
+use rtml::{tags::*, validate::validate, Children};
+
+#[test]
+fn whitespace_text_of_any_type_is_allowed() {
+    let list = ul(Children::from(("\n  ", li("a"), '\n', String::from(" "))));
+    assert!(validate(&list).is_empty());
+}
+
+#[test]
+fn other_text_is_flagged() {
+    let list = ul(Children::from(("a", li("b"))));
+    assert_eq!(validate(&list).len(), 1);
+}
+
+#[test]
+fn children_of_void_elements_are_flagged() {
+    let mut br = UnitTag::new("br");
+    br.push_child(" ");
+    br.push_child(span("x"));
+    let diagnostics = validate(&br);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "br: `#text` is not permitted in `br`"
+    );
+}